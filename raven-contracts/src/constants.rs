@@ -6,5 +6,31 @@ pub const STATE_SEED: &[u8] = b"state_v1";
 pub const ESCROW_SEED: &[u8] = b"escrow_vault";
 #[constant]
 pub const ROUND_SEED: &[u8] = b"Round";
+#[constant]
+pub const BUYBACK_SEED: &[u8] = b"buyback_vault";
+#[constant]
+pub const STAKING_SEED: &[u8] = b"staking_vault";
+#[constant]
+pub const USER_STATS_SEED: &[u8] = b"user_stats";
+#[constant]
+pub const HISTORY_SEED: &[u8] = b"market_history";
+#[constant]
+pub const ROLES_SEED: &[u8] = b"roles";
+#[constant]
+pub const ATTESTED_PRICE_SEED: &[u8] = b"attested_price";
+#[cfg(feature = "test-oracle")]
+#[constant]
+pub const MOCK_PRICE_SEED: &[u8] = b"mock_price";
 
 pub const ANCHOR_DISCRIMINATOR: usize = 8;
+pub const BPS_DENOMINATOR: u64 = 10_000;
+pub const MAX_CO_CREATORS: usize = 4;
+// market ids seed the per-market escrow vault
+pub const MAX_MARKET_ID_LEN: usize = 32;
+#[constant]
+pub const HISTORY_LEN: usize = 32;
+pub const MAX_ROLE_GRANTS: usize = 16;
+pub const MAX_SECONDARY_SOURCES: usize = 2;
+// prices from every oracle provider are scaled to this many decimals
+#[constant]
+pub const PRICE_DECIMALS: u32 = 8;
@@ -39,4 +39,36 @@ pub enum MarketError {
     PriceStale,
     #[msg("ConfidenceTooHigh")]
     ConfidenceTooHigh,
+
+    // new variants go at the end so existing error codes keep their numbers
+    #[msg("RoundInProgress")]
+    RoundInProgress,
+
+    #[msg("ProtocolPaused")]
+    ProtocolPaused,
+
+    #[msg("VaultNotMigrated")]
+    VaultNotMigrated,
+
+    #[msg("MissingRole")]
+    MissingRole,
+
+    // oracle
+    #[msg("InvalidOracleAccount")]
+    InvalidOracleAccount,
+    #[msg("InvalidOraclePrice")]
+    InvalidOraclePrice,
+    #[msg("InsufficientVerification")]
+    InsufficientVerification,
+    #[msg("PricePublishedLate")]
+    PricePublishedLate,
+
+    #[msg("MissingUserStats")]
+    MissingUserStats,
+
+    #[msg("PricePublishedEarly")]
+    PricePublishedEarly,
+
+    #[msg("MarketIdTooLong")]
+    MarketIdTooLong,
 }
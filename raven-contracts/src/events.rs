@@ -0,0 +1,337 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{CircuitBreaker, Direction, FeedStatus, OraclePolicy, OracleProvider, OracleSource, PythVerification, RevenueVault, Role, SettlementMode};
+
+// --------------------------
+//    protocol
+// --------------------------
+
+#[event]
+pub struct StateInitialized {
+    pub admin: Pubkey,
+    pub creator_fee_bps: u16,
+}
+
+#[event]
+pub struct AdminProposed {
+    pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>,
+}
+
+#[event]
+pub struct AdminAccepted {
+    pub previous_admin: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct StateConfigUpdated {
+    pub creator_fee_bps: u16,
+    pub guardian: Pubkey,
+}
+
+#[event]
+pub struct RoleGranted {
+    pub key: Pubkey,
+    pub role: Role,
+}
+
+#[event]
+pub struct RoleRevoked {
+    pub key: Pubkey,
+    pub role: Role,
+}
+
+#[event]
+pub struct StateMigrated {
+    pub feeds: u32,
+    pub markets: u32,
+}
+
+#[event]
+pub struct RevenueConfigSet {
+    pub operations_bps: u16,
+    pub buyback_bps: u16,
+    pub staking_bps: u16,
+    pub rm_mint: Pubkey,
+}
+
+#[event]
+pub struct RevenueDistributed {
+    pub operations: u64,
+    pub buyback: u64,
+    pub staking: u64,
+}
+
+#[event]
+pub struct RevenueWithdrawn {
+    pub vault: RevenueVault,
+    pub amount: u64,
+    pub recipient: Pubkey,
+    pub by: Pubkey,
+}
+
+#[event]
+pub struct BuybackBurned {
+    pub amount: u64,
+    pub total_burned: u64,
+}
+
+#[event]
+pub struct SolvencyChecked {
+    pub market_id: String,
+    pub vault_balance: u64,
+    pub open_stakes: u64,
+    pub unpaid_winnings: u64,
+    pub accrued_fees: u64,
+    pub bonds: u64,
+    pub liabilities: u64,
+    pub solvent: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolPaused {
+    pub by: Pubkey,
+}
+
+#[event]
+pub struct ProtocolResumed {
+    pub admin: Pubkey,
+}
+
+// --------------------------
+//    price feeds
+// --------------------------
+
+#[event]
+pub struct PriceFeedAdded {
+    pub symbol: String,
+    pub provider: OracleProvider,
+    pub feed_id: [u8; 32],
+    pub create_market_lamports: u64,
+    pub min_betting_lamports: u64,
+    pub min_betting_period: u16,
+    pub max_betting_period: u16,
+    pub min_settling_period: u16,
+    pub max_settling_period: u16,
+    pub min_creator_fee_bps: u16,
+    pub max_creator_fee_bps: u16,
+    pub creation_bond: bool,
+    pub secondary_sources: Vec<OracleSource>,
+    pub max_divergence_bps: u16,
+    pub min_verification: PythVerification,
+    pub outage_grace_period: u32,
+    pub breaker: CircuitBreaker,
+    pub oracle_limits: OraclePolicy,
+}
+
+#[event]
+pub struct PriceFeedUpdated {
+    pub symbol: String,
+    pub create_market_lamports: u64,
+    pub min_betting_lamports: u64,
+    pub min_betting_period: u16,
+    pub max_betting_period: u16,
+    pub min_settling_period: u16,
+    pub max_settling_period: u16,
+    pub secondary_sources: Vec<OracleSource>,
+    pub max_divergence_bps: u16,
+    pub min_verification: PythVerification,
+    pub outage_grace_period: u32,
+    pub breaker: CircuitBreaker,
+    pub oracle_limits: OraclePolicy,
+    pub apply_to_existing: bool,
+    pub markets_updated: u32,
+}
+
+#[event]
+pub struct PriceFeedStatusSet {
+    pub symbol: String,
+    pub status: FeedStatus,
+}
+
+#[event]
+pub struct PriceAttested {
+    pub feed_id: [u8; 32],
+    pub price: u64,
+    pub conf: u64,
+    pub publish_time: i64,
+    pub attester: Pubkey,
+}
+
+#[event]
+pub struct PriceFeedRemoved {
+    pub symbol: String,
+}
+
+// --------------------------
+//    markets
+// --------------------------
+
+#[event]
+pub struct MarketCreated {
+    pub market_id: String,
+    pub symbol: String,
+    pub creator: Pubkey,
+    pub provider: OracleProvider,
+    pub feed_id: [u8; 32],
+    pub fee_rate_bps: u16,
+    pub creator_fee_bps: u16,
+    pub betting_period: u16,
+    pub settling_period: u16,
+    pub settlement: SettlementMode,
+    pub oracle_policy: OraclePolicy,
+    pub bond_lamports: u64,
+}
+
+#[event]
+pub struct MarketPaused {
+    pub market_id: String,
+    pub by: Pubkey,
+}
+
+#[event]
+pub struct MarketResumed {
+    pub market_id: String,
+    pub by: Pubkey,
+}
+
+#[event]
+pub struct MarketRetired {
+    pub market_id: String,
+    pub bond_refunded: u64,
+}
+
+#[event]
+pub struct MarketBondSlashed {
+    pub market_id: String,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RoundMigrated {
+    pub market_id: String,
+    pub round_index: u32,
+    pub open_stakes: u64,
+    pub unpaid_winnings: u64,
+}
+
+#[event]
+pub struct MarketVaultMigrated {
+    pub market_id: String,
+    pub amount: u64,
+}
+
+#[event]
+pub struct CreatorFeeRecipientSet {
+    pub market_id: String,
+    pub recipient: Pubkey,
+}
+
+#[event]
+pub struct CreatorFeesClaimed {
+    pub market_id: String,
+    pub claimant: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+// --------------------------
+//    rounds
+// --------------------------
+
+#[event]
+pub struct BetPlaced {
+    pub market_id: String,
+    pub round_index: u32,
+    pub user: Pubkey,
+    pub direction: Direction,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RoundLocked {
+    pub market_id: String,
+    pub round_index: u32,
+    pub start_price: u64,
+    pub start_time: u32,
+    pub end_time: u32,
+}
+
+#[event]
+pub struct RoundSettled {
+    pub market_id: String,
+    pub round_index: u32,
+    pub start_price: u64,
+    pub end_price: u64,
+    pub winner: Option<Direction>,
+    pub total_up: u64,
+    pub total_down: u64,
+    pub total_fee: u64,
+    pub creator_fee: u64,
+    pub protocol_fee: u64,
+}
+
+#[event]
+pub struct PriceSampled {
+    pub market_id: String,
+    pub round_index: u32,
+    pub price: u64,
+    pub samples: u16,
+}
+
+#[event]
+pub struct RoundVoided {
+    pub market_id: String,
+    pub round_index: u32,
+    pub median_price: u64,
+    pub spread_bps: u64,
+}
+
+#[event]
+pub struct RoundStalled {
+    pub market_id: String,
+    pub round_index: u32,
+    pub end_time: u32,
+    pub refundable: u64,
+    pub voided_by: Pubkey,
+}
+
+#[event]
+pub struct RoundDisputed {
+    pub market_id: String,
+    pub round_index: u32,
+    pub start_price: u64,
+    pub end_price: u64,
+    pub reference_price: Option<u64>,
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub market_id: String,
+    pub round_index: u32,
+    pub upheld: bool,
+    pub by: Pubkey,
+}
+
+#[event]
+pub struct MarketPriceRestored {
+    pub market_id: String,
+    pub price: u64,
+}
+
+#[event]
+pub struct BetPaidOut {
+    pub market_id: String,
+    pub round_index: u32,
+    pub user: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RoundClosed {
+    pub market_id: String,
+    pub round_index: u32,
+    pub closed_by: Pubkey,
+}
@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+
+use crate::error::MarketError;
+use crate::events::*;
+use crate::constants::*;
+use crate::state::*;
+
+// --------------------------
+//    ProposeAdmin
+// --------------------------
+pub fn propose_admin_impl(ctx: Context<ProposeAdmin>, new_admin: Option<Pubkey>) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    state.propose_admin(new_admin)?;
+
+    emit!(AdminProposed {
+        admin: state.admin_pubkey,
+        pending_admin: new_admin,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED],
+        bump,
+        constraint = state.admin_pubkey == admin.key() @ MarketError::IllegalOwner
+    )]
+    pub state: Account<'info, State>,
+}
+
+// --------------------------
+//    AcceptAdmin
+// --------------------------
+pub fn accept_admin_impl(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let new_admin = ctx.accounts.new_admin.key();
+    let previous_admin = ctx.accounts.state.accept_admin(&new_admin)?;
+
+    emit!(AdminAccepted {
+        previous_admin,
+        admin: new_admin,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub new_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED],
+        bump
+    )]
+    pub state: Account<'info, State>,
+}
+
+// --------------------------
+//    UpdateStateConfig
+// --------------------------
+
+// Fields left as None are kept as they are.
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct UpdateStateConfigArgs {
+    creator_fee_bps: Option<u16>,
+    guardian: Option<Pubkey>,
+}
+
+pub fn update_state_config_impl(ctx: Context<UpdateStateConfig>, args: UpdateStateConfigArgs) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    if let Some(creator_fee_bps) = args.creator_fee_bps {
+        state.set_creator_fee_bps(creator_fee_bps)?;
+    }
+    if let Some(guardian) = args.guardian {
+        state.guardian = guardian;
+    }
+
+    emit!(StateConfigUpdated {
+        creator_fee_bps: state.creator_fee_bps,
+        guardian: state.guardian,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateStateConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED],
+        bump,
+        constraint = state.admin_pubkey == admin.key() @ MarketError::IllegalOwner
+    )]
+    pub state: Account<'info, State>,
+}
+
+// --------------------------
+//    GrantRole
+// --------------------------
+pub fn grant_role_impl(ctx: Context<GrantRole>, key: Pubkey, role: Role) -> Result<()> {
+    ctx.accounts.roles.grant(key, role)?;
+
+    emit!(RoleGranted { key, role });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct GrantRole<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [STATE_SEED],
+        bump,
+        constraint = state.admin_pubkey == admin.key() @ MarketError::IllegalOwner
+    )]
+    pub state: Account<'info, State>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = ANCHOR_DISCRIMINATOR + Roles::INIT_SPACE,
+        seeds = [ROLES_SEED],
+        bump
+    )]
+    pub roles: Account<'info, Roles>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// --------------------------
+//    RevokeRole
+// --------------------------
+pub fn revoke_role_impl(ctx: Context<RevokeRole>, key: Pubkey, role: Role) -> Result<()> {
+    ctx.accounts.roles.revoke(&key, role)?;
+
+    emit!(RoleRevoked { key, role });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [STATE_SEED],
+        bump,
+        constraint = state.admin_pubkey == admin.key() @ MarketError::IllegalOwner
+    )]
+    pub state: Account<'info, State>,
+
+    #[account(
+        mut,
+        seeds = [ROLES_SEED],
+        bump
+    )]
+    pub roles: Account<'info, Roles>,
+}
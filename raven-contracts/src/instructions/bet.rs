@@ -1,8 +1,10 @@
 use std::collections::BTreeMap;
 
 use anchor_lang::{prelude::*, solana_program::{program::invoke_signed, system_instruction}};
-use pyth_solana_receiver_sdk::price_update::{get_feed_id_from_hex, PriceUpdateV2};
 use crate::error::MarketError;
+use crate::events::*;
+use crate::instructions::revenue::{distribute_revenue, RevenueAccounts};
+use crate::oracle::{read_median, ReadPolicy};
 use crate::constants::*;
 use crate::state::*;
 
@@ -19,28 +21,44 @@ pub struct UserBetArgs {
     amount: u64,
 }
 
-pub fn user_bet_impl<'a>(ctx: Context<UserBet>, args: UserBetArgs) -> Result<()> {
+pub fn user_bet_impl(ctx: Context<UserBet>, args: UserBetArgs) -> Result<()> {
     require!(args.amount > 0, MarketError::InvalidArgument);
 
     let state = &mut ctx.accounts.state;
     let user = &ctx.accounts.user;
     let escrow = &ctx.accounts.escrow;
 
-    let mk_opt = state.markets.iter().find(|m| m.market_id == args.market_id);
+    require!(!state.paused, MarketError::ProtocolPaused);
+    require!(
+        matches!(state.market_feed_status(&args.market_id), Some(FeedStatus::Listed | FeedStatus::Deprecated)),
+        MarketError::InvalidPriceFeed
+    );
+
+    let mk_opt = state.markets.iter_mut().find(|m| m.market_id == args.market_id);
     require!(mk_opt.is_some(), MarketError::InvalidMarket);
     let mk = mk_opt.unwrap();
 
-    require!(!mk.paused, MarketError::MarketPaused);
+    require!(!mk.paused && !mk.awaiting_price, MarketError::MarketPaused);
+    require!(!mk.legacy_escrow, MarketError::VaultNotMigrated);
     require!(args.amount >= mk.min_betting_price, MarketError::InvalidArgument);
 
     let round = &mut ctx.accounts.round;
+    require!(round.market_id == mk.market_id, MarketError::InvalidMarket);
+    // a round voided when it opened is settled inside its betting window
+    require!(!round.settled, MarketError::InvalidTime);
 
     let now = Clock::get()?.unix_timestamp as u32;
-    require!(now <= round.start_time + mk.betting_period as u32, MarketError::InvalidTime);
+    require!(now <= round.betting_end, MarketError::InvalidTime);
 
+    let stats = &mut ctx.accounts.user_stats;
+    if !round.bets.iter().any(|b| b.user == user.key()) {
+        stats.rounds_played += 1;
+    }
+    stats.user = user.key();
+    stats.total_wagered = stats.total_wagered.saturating_add(args.amount);
 
     invoke_signed(
-        &system_instruction::transfer(user.key, &state.escrow_pubkey, args.amount),
+        &system_instruction::transfer(user.key, escrow.key, args.amount),
         &[
             user.to_account_info(),
             escrow.to_account_info(),
@@ -66,14 +84,15 @@ pub fn user_bet_impl<'a>(ctx: Context<UserBet>, args: UserBetArgs) -> Result<()>
         Direction::Up => round.total_up = round.total_up.saturating_add(args.amount),
         Direction::Down => round.total_down = round.total_down.saturating_add(args.amount),
     }
+    mk.open_stakes = mk.open_stakes.saturating_add(args.amount);
 
-    msg!(
-        "UserBet => market={}, user={}, amount={}, dir={:?}",
-        mk.market_id,
-        user.key(),
-        args.amount,
-        dir
-    );
+    emit!(BetPlaced {
+        market_id: args.market_id,
+        round_index: args.round_index,
+        user: user.key(),
+        direction: dir,
+        amount: args.amount,
+    });
     Ok(())
 }
 
@@ -97,14 +116,23 @@ pub struct UserBet<'info> {
     )]
     pub round: Account<'info, Round>,
 
-    /// CHECK: escrow_vault
+    /// CHECK: market escrow vault
     #[account(
         mut, 
-        seeds = [ESCROW_SEED],
+        seeds = [ESCROW_SEED, args.market_id.as_bytes()],
         bump
     )]
     pub escrow: AccountInfo<'info>,
 
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = ANCHOR_DISCRIMINATOR + UserStats::INIT_SPACE,
+        seeds = [USER_STATS_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -117,61 +145,164 @@ pub struct UserBet<'info> {
 pub struct ProcessRoundArgs {
     market_id: String,
     round_index: u32,
-    maximum_age: u32,
-    feed_id: String,
-    // time: u32,
-    // price: u64,
 }
 
-pub fn process_round_impl(ctx: Context<ProcessRound>, args: ProcessRoundArgs) -> Result<()> {
+pub fn process_round_impl<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ProcessRound<'info>>,
+    args: ProcessRoundArgs,
+) -> Result<()> {
     let system_program = &ctx.accounts.system_program;
-    let creater = &ctx.accounts.creater;
     let admin = &ctx.accounts.admin;
     let escrow = &ctx.accounts.escrow;
 
-    require!(*admin.key == ctx.accounts.state.admin_pubkey, MarketError::InvalidArgument);
+    ctx.accounts.state.require_role(ctx.accounts.roles.as_deref(), ctx.accounts.signer.key, Role::Keeper)?;
 
     let clock = Clock::get()?;
     let now = clock.unix_timestamp as u32;
-    // let now = args.time;
 
     let state = &mut ctx.accounts.state;
-    let escrow_bump = state.escrow_bump;
-    let c_percent = state.creator_fee_percent;
+    let escrow_bump = ctx.bumps.escrow;
+    let revenue_split = state.revenue_split;
+    let feed_status = state.market_feed_status(&args.market_id);
+    let (secondary_sources, max_divergence_bps, min_verification, breaker) = state.market_feed(&args.market_id)
+        .map(|pf| (pf.secondary_sources.clone(), pf.max_divergence_bps, pf.min_verification, pf.breaker))
+        .unwrap_or((vec![], 0, PythVerification::Full, CircuitBreaker::default()));
 
     let mk_opt = state.markets.iter_mut().find(|m| m.market_id == args.market_id);
     require!(mk_opt.is_some(), MarketError::InvalidMarket);
     let mk = mk_opt.unwrap();
-
-    require!(*creater.key == mk.creator_pubkey, MarketError::InvalidArgument);
+    require!(!mk.legacy_escrow, MarketError::VaultNotMigrated);
 
     let round = &mut ctx.accounts.round;
-    let price_update = &ctx.accounts.price_update;
-    let pyth_price = price_update.get_price_no_older_than(
-        &clock, args.maximum_age as u64, &get_feed_id_from_hex(&args.feed_id)?,
-    )?;
-    require!(pyth_price.price > 0, MarketError::InvalidPythPrice);
-    let price = pyth_price.price as u64;
-    // let price = args.price;
+    // rounds from before market_id was recorded go through migrate_round instead
+    if round.market_id.is_empty() && round.start_time == 0 {
+        round.market_id = mk.market_id.clone();
+    }
+    require!(round.market_id == mk.market_id, MarketError::InvalidMarket);
+
+    // secondary price accounts follow in remaining accounts, in the feed's order
+    let mut sources = vec![OracleSource { provider: mk.provider, feed_id: mk.feed_id }];
+    sources.extend(secondary_sources);
+    let mut price_accounts = vec![ctx.accounts.price_account.as_ref()];
+    price_accounts.extend(ctx.remaining_accounts.iter());
+    // a spot close must come from a price published between end_time and the publish delay
+    let mut closes_spot = mk.settlement == SettlementMode::Spot
+        && round.start_time > 0
+        && round.end_price == 0
+        && !round.voided
+        && now >= round.end_time;
+    // once no such price can still be read, the round voids instead of waiting forever
+    if closes_spot && mk.oracle_policy.close_window_missed(round.end_time, now) {
+        round.voided = true;
+        closes_spot = false;
+    }
+    let policy = ReadPolicy {
+        maximum_age: mk.oracle_policy.max_price_age as u64,
+        min_verification,
+        max_conf_bps: mk.oracle_policy.max_conf_bps,
+        published_after: closes_spot.then_some(round.end_time as i64),
+        published_by: closes_spot.then(|| round.end_time as i64 + mk.oracle_policy.max_publish_delay as i64),
+    };
+    let quote = read_median(&sources, &price_accounts, &clock, &policy)?;
+    let price = quote.price;
+    round.verification = PythVerification::weakest(round.verification, quote.verification);
+    if mk.awaiting_price {
+        mk.awaiting_price = false;
+        emit!(MarketPriceRestored {
+            market_id: args.market_id.clone(),
+            price,
+        });
+    }
+    let diverged = sources.len() > 1 && quote.spread_bps > max_divergence_bps as u64;
 
     if round.start_price == 0 && round.start_time == 0 {
-        round.start_price = price;
+        // only listed feeds open new rounds
+        require!(feed_status == Some(FeedStatus::Listed), MarketError::InvalidPriceFeed);
         round.start_time = now;
-        round.end_time = now + (mk.betting_period + mk.settling_period) as u32;
+        mk.open_rounds += 1;
+        if diverged {
+            // no bets can be in yet; the round settles empty right away
+            round.end_time = now;
+            round.betting_end = now;
+            round.voided = true;
+        } else {
+            round.start_price = price;
+            round.betting_end = now + mk.betting_period as u32;
+            round.end_time = round.betting_end + mk.settling_period as u32;
+        }
+
+        emit!(RoundLocked {
+            market_id: args.market_id.clone(),
+            round_index: args.round_index,
+            start_price: round.start_price,
+            start_time: round.start_time,
+            end_time: round.end_time,
+        });
     }
 
-    if round.end_price == 0 && now >= round.end_time {
-        round.end_price = price;
+    if let SettlementMode::Twap { sample_interval, .. } = mk.settlement {
+        let settling_start = round.betting_end;
+        let due = round.twap_samples == 0 || now >= round.last_sample_time + sample_interval as u32;
+        if round.start_price > 0 && !diverged && due && now >= settling_start && now <= round.end_time {
+            round.record_sample(price, now);
+            emit!(PriceSampled {
+                market_id: args.market_id.clone(),
+                round_index: args.round_index,
+                price,
+                samples: round.twap_samples,
+            });
+        }
     }
 
-    if round.end_price > 0 && !round.settled {
+    if round.end_price == 0 && !round.voided && now >= round.end_time {
+        match mk.settlement {
+            SettlementMode::Spot if diverged => round.voided = true,
+            SettlementMode::Spot => round.end_price = price,
+            SettlementMode::Twap { min_samples, .. } => match round.twap() {
+                Some(twap) if round.twap_samples >= min_samples && twap > 0 => round.end_price = twap,
+                _ => round.voided = true,
+            },
+        }
+        if round.end_price > 0 && breaker.trips(round.start_price, round.end_price, quote.reference) {
+            round.disputed_at = now;
+            emit!(RoundDisputed {
+                market_id: args.market_id.clone(),
+                round_index: args.round_index,
+                start_price: round.start_price,
+                end_price: round.end_price,
+                reference_price: quote.reference,
+            });
+        }
+    }
+
+    // settlement waits for resolve_dispute, or voids once the guardian has let it lapse
+    if round.disputed_at > 0 && !round.settled {
+        if !round.dispute_expired(now, breaker.dispute_timeout) {
+            return Ok(());
+        }
+        round.disputed_at = 0;
+        round.voided = true;
+    }
+
+    if round.voided && !round.settled {
+        emit!(RoundVoided {
+            market_id: args.market_id.clone(),
+            round_index: args.round_index,
+            median_price: price,
+            spread_bps: quote.spread_bps,
+        });
+    }
+
+    if (round.end_price > 0 || round.voided) && !round.settled {
         let sp = round.start_price;
         let ep = round.end_price;
         let total_up = round.total_up;
         let total_down = round.total_down;
 
-        let fee_rate = mk.fee_rate;
-        let winner_dir = if sp < ep {
+        let fee_rate_bps = mk.fee_rate_bps;
+        let winner_dir = if round.voided {
+            None
+        } else if sp < ep {
             Some(Direction::Up)
         } else if sp > ep {
             Some(Direction::Down)
@@ -179,6 +310,9 @@ pub fn process_round_impl(ctx: Context<ProcessRound>, args: ProcessRoundArgs) ->
             None
         };
 
+        let mut total_fee = 0;
+        let mut fee_creator = 0;
+        let mut fee_admin = 0;
         if let Some(wdir) = winner_dir {
             // loser_pool
             let loser_pool = if wdir == Direction::Up {
@@ -191,18 +325,28 @@ pub fn process_round_impl(ctx: Context<ProcessRound>, args: ProcessRoundArgs) ->
                     bet.result = bet.amount;
                 }
             } else {
-                let total_fee = loser_pool.saturating_mul(fee_rate as u64) / 100;
+                total_fee = bps_of(loser_pool, fee_rate_bps);
                 let distributable = loser_pool.saturating_sub(total_fee);
 
-                // creator_fee_percent
-                let fee_creator = total_fee.saturating_mul(c_percent as u64) / 100;
-                let fee_admin = total_fee.saturating_sub(fee_creator);
+                // creator_fee_bps
+                fee_creator = bps_of(total_fee, mk.creator_fee_bps);
+                fee_admin = total_fee.saturating_sub(fee_creator);
 
-                if fee_creator > 0 {
-                    transfer_sol(&escrow, &creater, fee_creator, system_program, escrow_bump)?;
-                }
+                mk.accrue_creator_fee(fee_creator);
                 if fee_admin > 0 {
-                    transfer_sol(&escrow, &admin, fee_admin, system_program, escrow_bump)?;
+                    let revenue_accounts = RevenueAccounts {
+                        operations: admin,
+                        buyback: &ctx.accounts.buyback_vault,
+                        staking: &ctx.accounts.staking_vault,
+                        system_program: system_program.as_ref(),
+                    };
+                    distribute_revenue(
+                        &revenue_split,
+                        fee_admin,
+                        escrow,
+                        &revenue_accounts,
+                        &[&[ESCROW_SEED, args.market_id.as_bytes(), &[escrow_bump]]],
+                    )?;
                 }
 
                 let winner_pool = if wdir == Direction::Up {
@@ -215,8 +359,9 @@ pub fn process_round_impl(ctx: Context<ProcessRound>, args: ProcessRoundArgs) ->
                 } else {
                     for bet in round.bets.iter_mut() {
                         if bet.direction == wdir {
-                            let ratio = bet.amount.saturating_mul(1000u64) / winner_pool;
-                            let share = distributable * ratio / 1000u64;
+                            let share = (distributable as u128 * bet.amount as u128)
+                                .checked_div(winner_pool as u128)
+                                .unwrap_or(0) as u64;
                             bet.result = bet.amount.saturating_add(share);
                         }
                     }
@@ -228,8 +373,42 @@ pub fn process_round_impl(ctx: Context<ProcessRound>, args: ProcessRoundArgs) ->
             }
         }
 
-        mk.round_index = mk.round_index + 1;
+        let payable = round.bets.iter().fold(0u64, |acc, b| acc.saturating_add(b.result));
+        mk.open_stakes = mk.open_stakes.saturating_sub(total_up.saturating_add(total_down));
+        mk.unpaid_winnings = mk.unpaid_winnings.saturating_add(payable);
+        mk.open_rounds = mk.open_rounds.saturating_sub(1);
+
+        mk.round_index += 1;
         round.settled = true;
+
+        let history = &mut ctx.accounts.history;
+        if history.market_id.is_empty() {
+            history.market_id = args.market_id.clone();
+        }
+        history.push(RoundOutcome {
+            round_index: args.round_index,
+            start_price: sp,
+            end_price: ep,
+            winner: winner_dir,
+            total_up,
+            total_down,
+            total_fee,
+            settled_at: now,
+            voided: round.voided,
+        });
+
+        emit!(RoundSettled {
+            market_id: args.market_id,
+            round_index: args.round_index,
+            start_price: sp,
+            end_price: ep,
+            winner: winner_dir,
+            total_up,
+            total_down,
+            total_fee,
+            creator_fee: fee_creator,
+            protocol_fee: fee_admin,
+        });
     }
 
     Ok(())
@@ -249,7 +428,14 @@ pub struct ProcessRound<'info> {
     )]
     pub state: Account<'info, State>,
 
-    pub price_update: Account<'info, PriceUpdateV2>,
+    /// CHECK: price account of the market's oracle provider, validated by oracle::PriceSource::read
+    pub price_account: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [ROLES_SEED],
+        bump
+    )]
+    pub roles: Option<Account<'info, Roles>>,
 
     #[account(
         init_if_needed, 
@@ -260,48 +446,262 @@ pub struct ProcessRound<'info> {
     )]
     pub round: Account<'info, Round>,
 
-    /// CHECK: market creater
-    #[account(mut)]
-    pub creater: AccountInfo<'info>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = ANCHOR_DISCRIMINATOR + MarketHistory::INIT_SPACE,
+        seeds = [HISTORY_SEED, args.market_id.as_bytes()],
+        bump
+    )]
+    pub history: Box<Account<'info, MarketHistory>>,
 
-    /// CHECK: market administrator
-    #[account(mut)]
+    /// CHECK: market administrator, receives the operations share of fees
+    #[account(
+        mut,
+        address = state.admin_pubkey @ MarketError::IllegalOwner
+    )]
     pub admin: AccountInfo<'info>,
 
-    /// CHECK: escrow_vault
+    /// CHECK: market escrow vault
     #[account(
         mut, 
-        seeds = [ESCROW_SEED],
+        seeds = [ESCROW_SEED, args.market_id.as_bytes()],
         bump
     )]
     pub escrow: AccountInfo<'info>,
 
+    /// CHECK: buyback_vault
+    #[account(
+        mut,
+        seeds = [BUYBACK_SEED],
+        bump
+    )]
+    pub buyback_vault: AccountInfo<'info>,
+
+    /// CHECK: staking_vault
+    #[account(
+        mut,
+        seeds = [STAKING_SEED],
+        bump
+    )]
+    pub staking_vault: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// --------------------------
+//    VoidRound
+// --------------------------
+
+/// Permissionless way out of an oracle outage: once a round is past its feed's grace period
+/// without a settling price, anyone may void it. Stakes become claimable as usual and the
+/// market takes no bets until process_round reads a fresh price. Disputed rounds the guardian
+/// has not reviewed within the breaker's timeout, and spot rounds past their closing publish
+/// window, can be voided here too.
+pub fn void_round_impl(ctx: Context<VoidRound>, market_id: String, round_index: u32) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    let round = &mut ctx.accounts.round;
+    let now = Clock::get()?.unix_timestamp as u32;
+
+    let (grace_period, dispute_timeout) = state.market_feed(&market_id)
+        .map(|pf| (pf.outage_grace_period, pf.breaker.dispute_timeout))
+        .unwrap_or_default();
+    let mk_opt = state.markets.iter_mut().find(|m| m.market_id == market_id);
+    require!(mk_opt.is_some(), MarketError::InvalidMarket);
+    let mk = mk_opt.unwrap();
+    require!(round.market_id == market_id, MarketError::InvalidMarket);
+    // a dispute nobody reviewed lapses the same way, without holding back the market
+    let stalled = round.is_stalled(now, grace_period);
+    // a spot round whose closing price can no longer be read is stuck even without a grace period
+    let close_missed = mk.settlement == SettlementMode::Spot
+        && round.start_time > 0
+        && round.end_price == 0
+        && !round.settled
+        && round.disputed_at == 0
+        && mk.oracle_policy.close_window_missed(round.end_time, now);
+    require!(
+        stalled || close_missed || round.dispute_expired(now, dispute_timeout),
+        MarketError::InvalidTime
+    );
+
+    let total_up = round.total_up;
+    let total_down = round.total_down;
+    let payable = round.void();
+    mk.open_stakes = mk.open_stakes.saturating_sub(total_up.saturating_add(total_down));
+    mk.unpaid_winnings = mk.unpaid_winnings.saturating_add(payable);
+    mk.open_rounds = mk.open_rounds.saturating_sub(1);
+    mk.round_index += 1;
+    mk.awaiting_price |= stalled;
+    round.disputed_at = 0;
+
+    let history = &mut ctx.accounts.history;
+    if history.market_id.is_empty() {
+        history.market_id = market_id.clone();
+    }
+    history.push(RoundOutcome {
+        round_index,
+        start_price: round.start_price,
+        end_price: round.end_price,
+        winner: None,
+        total_up,
+        total_down,
+        total_fee: 0,
+        settled_at: now,
+        voided: true,
+    });
+
+    emit!(RoundStalled {
+        market_id,
+        round_index,
+        end_time: round.end_time,
+        refundable: payable,
+        voided_by: ctx.accounts.signer.key(),
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: String, round_index: u32)]
+pub struct VoidRound<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED],
+        bump
+    )]
+    pub state: Account<'info, State>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_SEED, round_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = ANCHOR_DISCRIMINATOR + MarketHistory::INIT_SPACE,
+        seeds = [HISTORY_SEED, market_id.as_bytes()],
+        bump
+    )]
+    pub history: Box<Account<'info, MarketHistory>>,
+
     pub system_program: Program<'info, System>,
 }
 
+// --------------------------
+//    ResolveDispute
+// --------------------------
+
+/// Guardian review of a round held back by the circuit breaker. Upholding lets the next
+/// process_round settle on the disputed close price; rejecting voids the round instead.
+pub fn resolve_dispute_impl(ctx: Context<ResolveDispute>, market_id: String, round_index: u32, uphold: bool) -> Result<()> {
+    let round = &mut ctx.accounts.round;
+    require!(round.market_id == market_id, MarketError::InvalidMarket);
+    require!(round.disputed_at > 0 && !round.settled, MarketError::InvalidArgument);
+
+    round.disputed_at = 0;
+    round.voided = !uphold;
+
+    emit!(DisputeResolved {
+        market_id,
+        round_index,
+        upheld: uphold,
+        by: ctx.accounts.signer.key(),
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: String, round_index: u32)]
+pub struct ResolveDispute<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [STATE_SEED],
+        bump,
+        constraint = (state.guardian == signer.key() || state.admin_pubkey == signer.key()) @ MarketError::IllegalOwner
+    )]
+    pub state: Account<'info, State>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_SEED, round_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub round: Account<'info, Round>,
+}
+
 // --------------------------
 //    refund round
 // --------------------------
+
+/// Closes out every user whose UserStats account is passed in remaining accounts: records the
+/// round in their stats and pays any result to their wallet, passed alongside. Losing bets need
+/// only the stats account, so a crank can record every outcome without the bettor and then
+/// collect the round's rent through close_round. A wallet owed a result fails the call when its
+/// stats account is missing.
 pub fn refund_round_impl<'info>(
-    ctx: Context<'_, '_, '_, 'info, RefundRound<'info>>,
-    _market_id: String,
-    _round_index: u32,
+    ctx: Context<'_, '_, 'info, 'info, RefundRound<'info>>,
+    market_id: String,
+    round_index: u32,
 ) -> Result<()> {
-    let state = &ctx.accounts.state;
+    let state = &mut ctx.accounts.state;
     let round = &mut ctx.accounts.round;
+    let escrow_bump = ctx.bumps.escrow;
+
+    require!(round.market_id == market_id, MarketError::InvalidMarket);
+    require!(round.settled, MarketError::InvalidTime);
+    let mk_opt = state.markets.iter_mut().find(|m| m.market_id == market_id);
+    require!(mk_opt.is_some(), MarketError::InvalidMarket);
+    let mk = mk_opt.unwrap();
+    require!(!mk.legacy_escrow, MarketError::VaultNotMigrated);
+    let seeds: &[&[u8]] = &[ESCROW_SEED, market_id.as_bytes(), &[escrow_bump]];
 
     let mut pubkey_ai_map = BTreeMap::new();
+    let mut stats_map = BTreeMap::new();
     for ai in ctx.remaining_accounts.iter() {
+        if ai.owner == ctx.program_id {
+            if let Ok(stats) = Account::<UserStats>::try_from(ai) {
+                stats_map.insert(stats.user, stats);
+                continue;
+            }
+        }
         pubkey_ai_map.insert(ai.key, ai.to_account_info());
     }
+    for user in pubkey_ai_map.keys() {
+        require!(
+            round.payable_to(user) == 0 || stats_map.contains_key(*user),
+            MarketError::MissingUserStats
+        );
+    }
 
-    for bet in round.bets.iter_mut() {
-        if let Some(ai) = pubkey_ai_map.get(&bet.user) {
-            if !bet.refunded && bet.result > 0 {
-                transfer_sol(&ctx.accounts.escrow, &ai, bet.result, &ctx.accounts.system_program, state.escrow_bump)?;
-                bet.refunded = true;
-            }
+    for (user, stats) in stats_map.iter_mut() {
+        let ai_opt = pubkey_ai_map.get(user);
+        if round.payable_to(user) > 0 && ai_opt.is_none() {
+            continue;
+        }
+
+        let (staked, returned) = round.close_out(user);
+        if staked == 0 {
+            continue;
         }
+        if returned > 0 {
+            transfer_sol(&ctx.accounts.escrow, ai_opt.unwrap(), returned, &ctx.accounts.system_program, seeds)?;
+            mk.unpaid_winnings = mk.unpaid_winnings.saturating_sub(returned);
+
+            emit!(BetPaidOut {
+                market_id: market_id.clone(),
+                round_index,
+                user: *user,
+                amount: returned,
+            });
+        }
+        stats.record_round(staked, returned);
+        stats.exit(ctx.program_id)?;
     }
     Ok(())
 }
@@ -326,14 +726,91 @@ pub struct RefundRound<'info> {
     )]
     pub round: Account<'info, Round>,
 
-    /// CHECK: escrow_vault
+    /// CHECK: market escrow vault
     #[account(
         mut, 
-        seeds = [ESCROW_SEED],
+        seeds = [ESCROW_SEED, market_id.as_bytes()],
+        bump
+    )]
+    pub escrow: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// --------------------------
+//    claim round
+// --------------------------
+pub fn claim_round_impl(ctx: Context<ClaimRound>, market_id: String, round_index: u32) -> Result<()> {
+    let user = &ctx.accounts.user;
+    let state = &mut ctx.accounts.state;
+    let round = &mut ctx.accounts.round;
+
+    require!(round.market_id == market_id, MarketError::InvalidMarket);
+    require!(round.settled, MarketError::InvalidTime);
+    let mk_opt = state.markets.iter_mut().find(|m| m.market_id == market_id);
+    require!(mk_opt.is_some(), MarketError::InvalidMarket);
+    let mk = mk_opt.unwrap();
+    require!(!mk.legacy_escrow, MarketError::VaultNotMigrated);
+
+    let (staked, returned) = round.close_out(&user.key());
+    require!(staked > 0, MarketError::InvalidArgument);
+
+    if returned > 0 {
+        let seeds: &[&[u8]] = &[ESCROW_SEED, market_id.as_bytes(), &[ctx.bumps.escrow]];
+        transfer_sol(&ctx.accounts.escrow, user, returned, &ctx.accounts.system_program, seeds)?;
+        mk.unpaid_winnings = mk.unpaid_winnings.saturating_sub(returned);
+
+        emit!(BetPaidOut {
+            market_id,
+            round_index,
+            user: user.key(),
+            amount: returned,
+        });
+    }
+
+    let stats = &mut ctx.accounts.user_stats;
+    stats.user = user.key();
+    stats.record_round(staked, returned);
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: String, round_index: u32)]
+pub struct ClaimRound<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED],
+        bump
+    )]
+    pub state: Account<'info, State>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_SEED, round_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub round: Account<'info, Round>,
+
+    /// CHECK: market escrow vault
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, market_id.as_bytes()],
         bump
     )]
     pub escrow: AccountInfo<'info>,
 
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = ANCHOR_DISCRIMINATOR + UserStats::INIT_SPACE,
+        seeds = [USER_STATS_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -341,8 +818,19 @@ pub struct RefundRound<'info> {
 //    close round
 // --------------------------
 pub fn close_round_impl(
-    _ctx: Context<CloseRound>,
+    ctx: Context<CloseRound>,
+    round_index: u32,
 ) -> Result<()> {
+    // the market's ledger still counts stakes and results held in the round, and bettors'
+    // stats still miss it until every bet is closed out
+    let round = &ctx.accounts.round;
+    require!(round.settled && round.is_closed_out(), MarketError::RoundInProgress);
+
+    emit!(RoundClosed {
+        market_id: ctx.accounts.round.market_id.clone(),
+        round_index,
+        closed_by: ctx.accounts.signer.key(),
+    });
     Ok(())
 }
 
@@ -372,15 +860,14 @@ pub struct CloseRound<'info> {
 
 }
 
-fn transfer_sol<'info>(
+pub(crate) fn transfer_sol<'info>(
     escrow: &AccountInfo<'info>,
     to: &AccountInfo<'info>,
     amount: u64,
     system_program: &Program<'info, System>,
-    bump: u8,
+    seeds: &[&[u8]],
 ) -> Result<()> {
-    let seeds = &[ESCROW_SEED.as_ref(), &[bump]];
-    let ix = system_instruction::transfer(&escrow.key, &to.key, amount);
+    let ix = system_instruction::transfer(escrow.key, to.key, amount);
     invoke_signed(
         &ix,
         &[
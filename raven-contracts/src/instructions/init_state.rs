@@ -1,15 +1,19 @@
 use anchor_lang::prelude::*;
 
-use crate::error::MarketError;
+use crate::events::StateInitialized;
 use crate::constants::*;
 use crate::state::*;
 
-pub fn init_state_impl(ctx: Context<InitState>, admin_pubkey: Pubkey, creator_fee_percent: u8) -> Result<()> {
-    require!(creator_fee_percent <= 100, MarketError::InvalidArgument);
-
+pub fn init_state_impl(ctx: Context<InitState>, admin_pubkey: Pubkey, creator_fee_bps: u16) -> Result<()> {
     let state = &mut ctx.accounts.state;
     state.admin_pubkey = admin_pubkey;
-    state.creator_fee_percent = creator_fee_percent;
+    state.pending_admin = None;
+    state.set_creator_fee_bps(creator_fee_bps)?;
+    state.revenue_split = RevenueSplit::default();
+    state.rm_mint = Pubkey::default();
+    state.total_rm_burned = 0;
+    state.paused = false;
+    state.guardian = Pubkey::default();
 
     state.allowed_pricefeeds = vec![];
     state.markets = vec![];
@@ -18,11 +22,10 @@ pub fn init_state_impl(ctx: Context<InitState>, admin_pubkey: Pubkey, creator_fe
     state.escrow_pubkey = escrow_pubkey;
     state.escrow_bump = bump;
     
-    msg!(
-        "InitState => admin={:?}, creationFee={}",
-        admin_pubkey,
-        creator_fee_percent,
-    );
+    emit!(StateInitialized {
+        admin: admin_pubkey,
+        creator_fee_bps,
+    });
     Ok(())
 }
 
@@ -1,30 +1,51 @@
-use anchor_lang::{prelude::*, solana_program::system_instruction};
+use anchor_lang::{prelude::*, solana_program::{program::invoke_signed, system_instruction}};
 
 use crate::error::MarketError;
+use crate::events::*;
+use crate::instructions::bet::transfer_sol;
+use crate::instructions::revenue::{distribute_revenue, RevenueAccounts};
 use crate::constants::*;
 use crate::state::*;
 
 #[derive(AnchorDeserialize, AnchorSerialize)]
 pub struct CreateMarketArgs {
-    fee_rate: u8,
+    fee_rate_bps: u16,
     betting_period: u16,
     settling_period: u16,
     market_id: String,
     symbol: String,
+    // falls back to State.creator_fee_bps
+    creator_fee_bps: Option<u16>,
+    creator_weight: u16,
+    co_creators: Vec<CoCreatorArgs>,
+    settlement: SettlementMode,
+    // falls back to the feed's oracle_limits
+    oracle_policy: Option<OraclePolicy>,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct CoCreatorArgs {
+    recipient: Pubkey,
+    weight: u16,
 }
 
 pub fn create_market_impl(ctx: Context<CreateMarket>, args: CreateMarketArgs) -> Result<()> {
-    require!(args.fee_rate <= 100, MarketError::InvalidArgument);
+    require!(args.fee_rate_bps as u64 <= BPS_DENOMINATOR, MarketError::InvalidArgument);
+    require!(
+        !args.market_id.is_empty() && args.market_id.len() <= MAX_MARKET_ID_LEN,
+        MarketError::InvalidArgument
+    );
 
     let state = &mut ctx.accounts.state;
     let creator = &ctx.accounts.creator;
 
-    require!(args.fee_rate <= 100, MarketError::InvalidArgument);
+    require!(!state.paused, MarketError::ProtocolPaused);
 
     let pf_opt = state.allowed_pricefeeds.iter()
         .find(|pf| pf.symbol == args.symbol);
     require!(pf_opt.is_some(), MarketError::InvalidPriceFeed);
     let pf = pf_opt.unwrap();
+    require!(pf.status == FeedStatus::Listed, MarketError::InvalidPriceFeed);
 
     require!(
         (args.betting_period >= pf.min_betting_period)
@@ -33,21 +54,62 @@ pub fn create_market_impl(ctx: Context<CreateMarket>, args: CreateMarketArgs) ->
             && (args.settling_period <= pf.max_settling_period),
         MarketError::InvalidArgument
     );
+    require!(args.settlement.is_valid(args.settling_period), MarketError::InvalidArgument);
+    let oracle_policy = args.oracle_policy.unwrap_or(pf.oracle_limits);
+    require!(
+        oracle_policy.is_valid() && oracle_policy.within(&pf.oracle_limits),
+        MarketError::InvalidArgument
+    );
     require!(*ctx.accounts.admin.key == state.admin_pubkey, MarketError::InvalidArgument);
 
-    if pf.create_market_lamports > 0 {
-        let ix = system_instruction::transfer(
-            creator.key,
-            ctx.accounts.admin.key,
-            pf.create_market_lamports,
+    let creator_fee_bps = args.creator_fee_bps.unwrap_or(state.creator_fee_bps);
+    require!(
+        creator_fee_bps >= pf.min_creator_fee_bps && creator_fee_bps <= pf.max_creator_fee_bps,
+        MarketError::InvalidArgument
+    );
+
+    require!(args.co_creators.len() <= MAX_CO_CREATORS, MarketError::InvalidArgument);
+    let mut co_creators: Vec<CoCreator> = Vec::with_capacity(args.co_creators.len());
+    for co in &args.co_creators {
+        require!(co.weight > 0, MarketError::InvalidArgument);
+        require!(
+            co.recipient != creator.key() && !co_creators.iter().any(|c| c.recipient == co.recipient),
+            MarketError::InvalidArgument
         );
-        anchor_lang::solana_program::program::invoke_signed(
-            &ix,
+        co_creators.push(CoCreator {
+            recipient: co.recipient,
+            weight: co.weight,
+            fees_accrued: 0,
+        });
+    }
+
+    // the creator funds the vault's rent-exempt reserve, plus the bond when the feed asks for one
+    let rent_reserve = Rent::get()?.minimum_balance(0).saturating_sub(ctx.accounts.escrow.lamports());
+    let bond_lamports = if pf.creation_bond { pf.create_market_lamports } else { 0 };
+    if rent_reserve + bond_lamports > 0 {
+        invoke_signed(
+            &system_instruction::transfer(creator.key, ctx.accounts.escrow.key, rent_reserve + bond_lamports),
             &[
                 creator.to_account_info(),
-                ctx.accounts.admin.clone(),
-                // ctx.accounts.system_program.to_account_info(),
-            ], &[]
+                ctx.accounts.escrow.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            &[],
+        )?;
+    }
+    if pf.create_market_lamports > 0 && !pf.creation_bond {
+        let revenue_accounts = RevenueAccounts {
+            operations: &ctx.accounts.admin,
+            buyback: &ctx.accounts.buyback_vault,
+            staking: &ctx.accounts.staking_vault,
+            system_program: ctx.accounts.system_program.as_ref(),
+        };
+        distribute_revenue(
+            &state.revenue_split,
+            pf.create_market_lamports,
+            &creator.to_account_info(),
+            &revenue_accounts,
+            &[],
         )?;
     }
 
@@ -60,24 +122,54 @@ pub fn create_market_impl(ctx: Context<CreateMarket>, args: CreateMarketArgs) ->
     let now = Clock::get()?.unix_timestamp as u32;
     let new_mk = Market {
         market_id: args.market_id.clone(),
-        pyth_feed_id: pf.pyth_feed_id.clone(),
+        provider: pf.provider,
+        feed_id: pf.feed_id,
         creation_time: now,
         paused: false,
-        fee_rate: args.fee_rate,
+        fee_rate_bps: args.fee_rate_bps,
         min_betting_price: pf.min_betting_lamports,
         betting_period: args.betting_period,
         settling_period: args.settling_period,
         creator_pubkey: creator.key(),
+        creator_fee_recipient: creator.key(),
+        creator_fees_accrued: 0,
+        creator_fee_bps,
+        creator_weight: args.creator_weight,
+        co_creators,
+        bond_lamports,
+        open_stakes: 0,
+        unpaid_winnings: 0,
+        open_rounds: 0,
+        legacy_escrow: false,
+        settlement: args.settlement,
+        awaiting_price: false,
+        oracle_policy,
         round_index: 0,
         // current_round: null_mut(),
     };
+    let provider = new_mk.provider;
+    let feed_id = new_mk.feed_id;
     state.markets.push(new_mk);
 
-    msg!("CreateMarket => {}", args.market_id);
+    emit!(MarketCreated {
+        market_id: args.market_id,
+        symbol: args.symbol,
+        creator: creator.key(),
+        provider,
+        feed_id,
+        fee_rate_bps: args.fee_rate_bps,
+        creator_fee_bps,
+        betting_period: args.betting_period,
+        settling_period: args.settling_period,
+        settlement: args.settlement,
+        oracle_policy,
+        bond_lamports,
+    });
     Ok(())
 }
 
 #[derive(Accounts)]
+#[instruction(args: CreateMarketArgs)]
 pub struct CreateMarket<'info> {
     #[account(mut)]
     pub creator: Signer<'info>,
@@ -93,6 +185,30 @@ pub struct CreateMarket<'info> {
     )]
     pub state: Account<'info, State>,
 
+    /// CHECK: market escrow vault, holds the creation bond
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, args.market_id.as_bytes()],
+        bump
+    )]
+    pub escrow: AccountInfo<'info>,
+
+    /// CHECK: buyback_vault
+    #[account(
+        mut,
+        seeds = [BUYBACK_SEED],
+        bump
+    )]
+    pub buyback_vault: AccountInfo<'info>,
+
+    /// CHECK: staking_vault
+    #[account(
+        mut,
+        seeds = [STAKING_SEED],
+        bump
+    )]
+    pub staking_vault: AccountInfo<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -101,24 +217,30 @@ pub struct CreateMarket<'info> {
 // --------------------------
 pub fn pause_market_impl(ctx: Context<PauseMarket>, market_id: String) -> Result<()> {
     let signer = &ctx.accounts.signer;
-    let is_admin_user = signer.key() == ctx.accounts.state.admin_pubkey && signer.is_signer;
+    let state = &mut ctx.accounts.state;
+    let can_pause = state.require_role(ctx.accounts.roles.as_deref(), signer.key, Role::Pauser);
 
-    let mk_opt = ctx.accounts.state.markets.iter_mut().find(|m| m.market_id == market_id);
+    let mk_opt = state.markets.iter_mut().find(|m| m.market_id == market_id);
     require!(mk_opt.is_some(), MarketError::InvalidMarket);
     let mk = mk_opt.unwrap();
 
-    let is_creator_user = signer.key() == mk.creator_pubkey && signer.is_signer;
-    require!((is_admin_user || is_creator_user), MarketError::IllegalOwner);
+    // creators may always pause their own market
+    if signer.key() != mk.creator_pubkey {
+        can_pause?;
+    }
 
     mk.paused = true;
-    msg!("PauseMarket => {}", mk.market_id);
+    emit!(MarketPaused {
+        market_id,
+        by: signer.key(),
+    });
     Ok(())
 }
 
 #[derive(Accounts)]
 pub struct PauseMarket<'info> {
     #[account(mut)]
-    pub signer: Signer<'info>, // 可能是 creator 或 admin
+    pub signer: Signer<'info>, // 可能是 creator 或 pauser
 
     #[account(
         mut,
@@ -126,6 +248,12 @@ pub struct PauseMarket<'info> {
         bump
     )]
     pub state: Account<'info, State>,
+
+    #[account(
+        seeds = [ROLES_SEED],
+        bump
+    )]
+    pub roles: Option<Account<'info, Roles>>,
 }
 
 // --------------------------
@@ -133,28 +261,303 @@ pub struct PauseMarket<'info> {
 // --------------------------
 pub fn resume_market_impl(ctx: Context<ResumeMarket>, market_id: String) -> Result<()> {
     let state = &mut ctx.accounts.state;
-    let admin = &ctx.accounts.admin;
-
-    require!(admin.key() == state.admin_pubkey && admin.is_signer, MarketError::IllegalOwner);
+    let signer = &ctx.accounts.signer;
+    state.require_role(ctx.accounts.roles.as_deref(), signer.key, Role::MarketOperator)?;
 
     let mk_opt = state.markets.iter_mut().find(|m| m.market_id == market_id);
     require!(mk_opt.is_some(), MarketError::InvalidMarket);
     mk_opt.unwrap().paused = false;
 
-    msg!("ResumeMarket => {}", market_id);
+    emit!(MarketResumed {
+        market_id,
+        by: signer.key(),
+    });
     Ok(())
 }
 
 #[derive(Accounts)]
 pub struct ResumeMarket<'info> {
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED],
+        bump
+    )]
+    pub state: Account<'info, State>,
+
+    #[account(
+        seeds = [ROLES_SEED],
+        bump
+    )]
+    pub roles: Option<Account<'info, Roles>>,
+}
+
+// --------------------------
+//    SetCreatorFeeRecipient
+// --------------------------
+pub fn set_creator_fee_recipient_impl(ctx: Context<SetCreatorFeeRecipient>, market_id: String, recipient: Pubkey) -> Result<()> {
+    let creator = &ctx.accounts.creator;
+
+    let mk_opt = ctx.accounts.state.markets.iter_mut().find(|m| m.market_id == market_id);
+    require!(mk_opt.is_some(), MarketError::InvalidMarket);
+    let mk = mk_opt.unwrap();
+
+    require!(creator.key() == mk.creator_pubkey, MarketError::IllegalOwner);
+    mk.creator_fee_recipient = recipient;
+
+    emit!(CreatorFeeRecipientSet { market_id, recipient });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetCreatorFeeRecipient<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED],
+        bump
+    )]
+    pub state: Account<'info, State>,
+}
+
+// --------------------------
+//    ClaimCreatorFees
+// --------------------------
+pub fn claim_creator_fees_impl(ctx: Context<ClaimCreatorFees>, market_id: String) -> Result<()> {
+    let signer = &ctx.accounts.signer;
+    let recipient = &ctx.accounts.recipient;
+    let state = &mut ctx.accounts.state;
+    let escrow_bump = ctx.bumps.escrow;
+
+    let mk_opt = state.markets.iter_mut().find(|m| m.market_id == market_id);
+    require!(mk_opt.is_some(), MarketError::InvalidMarket);
+    let mk = mk_opt.unwrap();
+    require!(!mk.legacy_escrow, MarketError::VaultNotMigrated);
+
+    // the creator claims to its configured recipient, co-creators claim to themselves
+    let amount = if signer.key() == mk.creator_pubkey {
+        require!(recipient.key() == mk.creator_fee_recipient, MarketError::InvalidArgument);
+        std::mem::take(&mut mk.creator_fees_accrued)
+    } else {
+        let co_opt = mk.co_creators.iter_mut().find(|c| c.recipient == signer.key());
+        require!(co_opt.is_some(), MarketError::IllegalOwner);
+        require!(recipient.key() == signer.key(), MarketError::InvalidArgument);
+        std::mem::take(&mut co_opt.unwrap().fees_accrued)
+    };
+    require!(amount > 0, MarketError::InvalidArgument);
+
+    let seeds: &[&[u8]] = &[ESCROW_SEED, market_id.as_bytes(), &[escrow_bump]];
+    transfer_sol(&ctx.accounts.escrow, recipient, amount, &ctx.accounts.system_program, seeds)?;
+
+    emit!(CreatorFeesClaimed {
+        market_id,
+        claimant: signer.key(),
+        recipient: recipient.key(),
+        amount,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: String)]
+pub struct ClaimCreatorFees<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>, // creator or co-creator
+
+    /// CHECK: creator_fee_recipient for the creator, the signer itself for a co-creator
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED],
+        bump
+    )]
+    pub state: Account<'info, State>,
+
+    /// CHECK: market escrow vault
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, market_id.as_bytes()],
+        bump
+    )]
+    pub escrow: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// --------------------------
+//    RetireMarket
+// --------------------------
+/// Removes a paused market with nothing left at stake and refunds the creator's bond. Fees
+/// co-creators have not claimed are paid out here, to their wallets passed in remaining accounts.
+pub fn retire_market_impl<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RetireMarket<'info>>,
+    market_id: String,
+) -> Result<()> {
+    let creator = &ctx.accounts.creator;
+    let state = &mut ctx.accounts.state;
+
+    let pos_opt = state.markets.iter().position(|m| m.market_id == market_id);
+    require!(pos_opt.is_some(), MarketError::InvalidMarket);
+    let pos = pos_opt.unwrap();
+    let mk = &state.markets[pos];
+
+    require!(creator.key() == mk.creator_pubkey, MarketError::IllegalOwner);
+    require!(mk.paused, MarketError::InvalidArgument);
+    // the creator's own fees would be stranded once the market is gone
+    require!(mk.creator_fees_accrued == 0, MarketError::InvalidArgument);
+    require!(
+        mk.open_rounds == 0 && mk.open_stakes == 0 && mk.unpaid_winnings == 0,
+        MarketError::RoundInProgress
+    );
+    require!(!mk.legacy_escrow, MarketError::VaultNotMigrated);
+
+    let mk = state.markets.remove(pos);
+    let seeds: &[&[u8]] = &[ESCROW_SEED, market_id.as_bytes(), &[ctx.bumps.escrow]];
+
+    for co in mk.co_creators.iter().filter(|c| c.fees_accrued > 0) {
+        let ai_opt = ctx.remaining_accounts.iter().find(|ai| *ai.key == co.recipient);
+        require!(ai_opt.is_some(), MarketError::InvalidArgument);
+        transfer_sol(&ctx.accounts.escrow, ai_opt.unwrap(), co.fees_accrued, &ctx.accounts.system_program, seeds)?;
+
+        emit!(CreatorFeesClaimed {
+            market_id: market_id.clone(),
+            claimant: co.recipient,
+            recipient: co.recipient,
+            amount: co.fees_accrued,
+        });
+    }
+
+    // the vault's rent reserve and any unclaimed leftovers stay behind
+    let bond = mk.bond_lamports;
+    if bond > 0 {
+        transfer_sol(&ctx.accounts.escrow, creator, bond, &ctx.accounts.system_program, seeds)?;
+    }
+
+    emit!(MarketRetired {
+        market_id,
+        bond_refunded: bond,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: String)]
+pub struct RetireMarket<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED],
+        bump
+    )]
+    pub state: Account<'info, State>,
+
+    /// CHECK: market escrow vault
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, market_id.as_bytes()],
+        bump
+    )]
+    pub escrow: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// --------------------------
+//    SlashMarketBond
+// --------------------------
+pub fn slash_market_bond_impl(ctx: Context<SlashMarketBond>, market_id: String) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    state.require_role(ctx.accounts.roles.as_deref(), ctx.accounts.signer.key, Role::MarketOperator)?;
+    let escrow_bump = ctx.bumps.escrow;
+    let revenue_split = state.revenue_split;
+
+    let mk_opt = state.markets.iter_mut().find(|m| m.market_id == market_id);
+    require!(mk_opt.is_some(), MarketError::InvalidMarket);
+    let mk = mk_opt.unwrap();
+    require!(!mk.legacy_escrow, MarketError::VaultNotMigrated);
+
+    let bond = std::mem::take(&mut mk.bond_lamports);
+    require!(bond > 0, MarketError::InvalidArgument);
+    mk.paused = true;
+
+    let revenue_accounts = RevenueAccounts {
+        operations: &ctx.accounts.admin,
+        buyback: &ctx.accounts.buyback_vault,
+        staking: &ctx.accounts.staking_vault,
+        system_program: ctx.accounts.system_program.as_ref(),
+    };
+    distribute_revenue(
+        &revenue_split,
+        bond,
+        &ctx.accounts.escrow,
+        &revenue_accounts,
+        &[&[ESCROW_SEED, market_id.as_bytes(), &[escrow_bump]]],
+    )?;
+
+    emit!(MarketBondSlashed {
+        market_id,
+        amount: bond,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: String)]
+pub struct SlashMarketBond<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
 
     #[account(
         mut,
         seeds = [STATE_SEED],
-        bump,
-        constraint = state.admin_pubkey == admin.key() @ MarketError::IllegalOwner
+        bump
     )]
     pub state: Account<'info, State>,
+
+    #[account(
+        seeds = [ROLES_SEED],
+        bump
+    )]
+    pub roles: Option<Account<'info, Roles>>,
+
+    /// CHECK: operations revenue recipient
+    #[account(
+        mut,
+        address = state.admin_pubkey @ MarketError::IllegalOwner
+    )]
+    pub admin: AccountInfo<'info>,
+
+    /// CHECK: market escrow vault
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, market_id.as_bytes()],
+        bump
+    )]
+    pub escrow: AccountInfo<'info>,
+
+    /// CHECK: buyback_vault
+    #[account(
+        mut,
+        seeds = [BUYBACK_SEED],
+        bump
+    )]
+    pub buyback_vault: AccountInfo<'info>,
+
+    /// CHECK: staking_vault
+    #[account(
+        mut,
+        seeds = [STAKING_SEED],
+        bump
+    )]
+    pub staking_vault: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
 }
@@ -0,0 +1,231 @@
+use anchor_lang::{prelude::*, solana_program::{program::invoke_signed, system_instruction}, Discriminator};
+
+use crate::error::MarketError;
+use crate::events::*;
+use crate::instructions::bet::transfer_sol;
+use crate::constants::*;
+use crate::legacy::LegacyState;
+use crate::state::*;
+
+// --------------------------
+//    MigrateState
+// --------------------------
+pub fn migrate_state_impl(ctx: Context<MigrateState>) -> Result<()> {
+    let admin = &ctx.accounts.admin;
+    let state_ai = ctx.accounts.state.to_account_info();
+    require!(state_ai.owner == ctx.program_id, MarketError::IllegalOwner);
+
+    let migrated = {
+        let data = state_ai.try_borrow_data()?;
+        require!(
+            data.len() > ANCHOR_DISCRIMINATOR && data[..ANCHOR_DISCRIMINATOR] == State::DISCRIMINATOR,
+            MarketError::InvalidAccountData
+        );
+        // already in the current layout
+        if State::try_deserialize(&mut &data[..]).is_ok() {
+            return err!(MarketError::AccountAlreadyInitialized);
+        }
+        let legacy = LegacyState::deserialize(&mut &data[ANCHOR_DISCRIMINATOR..])
+            .map_err(|_| MarketError::InvalidAccountData)?;
+        require!(legacy.admin_pubkey == admin.key(), MarketError::IllegalOwner);
+        legacy.into_current()?
+    };
+
+    // the current layout is larger than the legacy one; the admin pays rent for the growth
+    let needed = ANCHOR_DISCRIMINATOR
+        + migrated.try_to_vec().map_err(|_| MarketError::InvalidAccountData)?.len();
+    if needed > state_ai.data_len() {
+        let top_up = Rent::get()?.minimum_balance(needed).saturating_sub(state_ai.lamports());
+        if top_up > 0 {
+            invoke_signed(
+                &system_instruction::transfer(admin.key, state_ai.key, top_up),
+                &[
+                    admin.to_account_info(),
+                    state_ai.clone(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+                &[],
+            )?;
+        }
+        state_ai.realloc(needed, false)?;
+    }
+
+    let mut data = state_ai.try_borrow_mut_data()?;
+    data[ANCHOR_DISCRIMINATOR..].fill(0);
+    let mut writer = &mut data[ANCHOR_DISCRIMINATOR..];
+    migrated.serialize(&mut writer).map_err(|_| MarketError::InvalidAccountData)?;
+
+    emit!(StateMigrated {
+        feeds: migrated.allowed_pricefeeds.len() as u32,
+        markets: migrated.markets.len() as u32,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateState<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: state in the legacy layout, verified by owner and discriminator
+    #[account(
+        mut,
+        seeds = [STATE_SEED],
+        bump
+    )]
+    pub state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// --------------------------
+//    MigrateRound
+// --------------------------
+/// Assigns a round created before rounds recorded their market, and books what it still holds
+/// into that market's ledger so bets, settlement, claims and vault migration can use it again.
+/// Rounds locked but not yet settled are voided.
+pub fn migrate_round_impl(ctx: Context<MigrateRound>, market_id: String, round_index: u32) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    state.require_role(ctx.accounts.roles.as_deref(), ctx.accounts.signer.key, Role::Treasurer)?;
+
+    let round = &mut ctx.accounts.round;
+    require!(round.market_id.is_empty(), MarketError::AccountAlreadyInitialized);
+    let mk_opt = state.markets.iter_mut().find(|m| m.market_id == market_id);
+    require!(mk_opt.is_some(), MarketError::InvalidMarket);
+    let mk = mk_opt.unwrap();
+
+    let (open_stakes, unpaid_winnings) = if round.settled {
+        // UserStats did not exist yet; bets with nothing owed are closed out as they are
+        for bet in round.bets.iter_mut().filter(|b| b.result == 0) {
+            bet.refunded = true;
+        }
+        (0, round.unpaid())
+    } else if round.start_time > 0 {
+        // the start price predates scaling to PRICE_DECIMALS and cannot be compared with a
+        // scaled close, so the round is voided and every stake returned
+        let payable = round.void();
+        if round_index == mk.round_index {
+            mk.round_index += 1;
+        }
+        (0, payable)
+    } else {
+        (round.total_up.saturating_add(round.total_down), 0)
+    };
+    mk.open_stakes = mk.open_stakes.saturating_add(open_stakes);
+    mk.unpaid_winnings = mk.unpaid_winnings.saturating_add(unpaid_winnings);
+    round.market_id = market_id.clone();
+
+    emit!(RoundMigrated {
+        market_id,
+        round_index,
+        open_stakes,
+        unpaid_winnings,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: String, round_index: u32)]
+pub struct MigrateRound<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED],
+        bump
+    )]
+    pub state: Account<'info, State>,
+
+    #[account(
+        seeds = [ROLES_SEED],
+        bump
+    )]
+    pub roles: Option<Account<'info, Roles>>,
+
+    #[account(
+        mut,
+        seeds = [ROUND_SEED, round_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub round: Account<'info, Round>,
+}
+
+// --------------------------
+//    MigrateMarketVault
+// --------------------------
+/// Moves what the market's vault is short of its liabilities out of the global escrow. Book the
+/// market's legacy rounds with migrate_round first; a round booked later leaves the vault short
+/// again, and calling this once more tops it up.
+pub fn migrate_market_vault_impl(ctx: Context<MigrateMarketVault>, market_id: String) -> Result<()> {
+    let signer = &ctx.accounts.signer;
+    let escrow = &ctx.accounts.escrow;
+    let state = &mut ctx.accounts.state;
+    state.require_role(ctx.accounts.roles.as_deref(), signer.key, Role::Treasurer)?;
+    let legacy_seeds: &[&[u8]] = &[ESCROW_SEED, &[state.escrow_bump]];
+
+    let mk_opt = state.markets.iter_mut().find(|m| m.market_id == market_id);
+    require!(mk_opt.is_some(), MarketError::InvalidMarket);
+    let mk = mk_opt.unwrap();
+    let reserve = Rent::get()?.minimum_balance(0);
+    let amount = mk.liabilities().saturating_sub(escrow.lamports().saturating_sub(reserve));
+    require!(mk.legacy_escrow || amount > 0, MarketError::AccountAlreadyInitialized);
+
+    let rent_reserve = reserve.saturating_sub(escrow.lamports());
+    if rent_reserve > 0 {
+        invoke_signed(
+            &system_instruction::transfer(signer.key, escrow.key, rent_reserve),
+            &[
+                signer.to_account_info(),
+                escrow.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            &[],
+        )?;
+    }
+
+    if amount > 0 {
+        transfer_sol(&ctx.accounts.legacy_escrow, escrow, amount, &ctx.accounts.system_program, legacy_seeds)?;
+    }
+    mk.legacy_escrow = false;
+
+    emit!(MarketVaultMigrated { market_id, amount });
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: String)]
+pub struct MigrateMarketVault<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED],
+        bump
+    )]
+    pub state: Account<'info, State>,
+
+    #[account(
+        seeds = [ROLES_SEED],
+        bump
+    )]
+    pub roles: Option<Account<'info, Roles>>,
+
+    /// CHECK: global escrow_vault shared by markets created before per-market vaults
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED],
+        bump
+    )]
+    pub legacy_escrow: AccountInfo<'info>,
+
+    /// CHECK: market escrow vault
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, market_id.as_bytes()],
+        bump
+    )]
+    pub escrow: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
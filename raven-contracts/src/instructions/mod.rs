@@ -2,8 +2,18 @@ pub mod init_state;
 pub mod price_feed;
 pub mod market;
 pub mod bet;
+pub mod revenue;
+pub mod migrate;
+pub mod solvency;
+pub mod query;
+pub mod admin;
 
 pub use init_state::*;
 pub use price_feed::*;
 pub use market::*;
 pub use bet::*;
+pub use revenue::*;
+pub use migrate::*;
+pub use solvency::*;
+pub use query::*;
+pub use admin::*;
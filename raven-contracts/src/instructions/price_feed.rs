@@ -1,48 +1,112 @@
 use anchor_lang::prelude::*;
 
 use crate::error::MarketError;
+use crate::events::*;
 use crate::constants::*;
 use crate::state::*;
 
 #[derive(AnchorDeserialize, AnchorSerialize)]
 pub struct AddPriceFeedArgs {
     symbol: String,
-    pyth_feed_id: String,
+    provider: OracleProvider,
+    // parsed by the provider, see oracle::PriceSource::parse_feed_id
+    feed_id: String,
     min_bet_period: u16,
     max_bet_period: u16,
     min_settle_period: u16,
     max_settle_period: u16,
     create_market_lamports: u64,
     min_betting_lamports: u64,
+    min_creator_fee_bps: u16,
+    max_creator_fee_bps: u16,
+    creation_bond: bool,
+    secondary_sources: Vec<OracleSourceArgs>,
+    max_divergence_bps: u16,
+    min_verification: PythVerification,
+    outage_grace_period: u32,
+    breaker: CircuitBreaker,
+    oracle_limits: OraclePolicy,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct OracleSourceArgs {
+    provider: OracleProvider,
+    feed_id: String,
+}
+
+fn parse_sources(args: &[OracleSourceArgs]) -> Result<Vec<OracleSource>> {
+    args.iter()
+        .map(|s| Ok(OracleSource { provider: s.provider, feed_id: s.provider.parse_feed_id(&s.feed_id)? }))
+        .collect()
 }
 
 pub fn add_price_feed_impl(ctx: Context<AddPriceFeed>, args: AddPriceFeedArgs) -> Result<()> {
     let state = &mut ctx.accounts.state;
+    state.require_role(ctx.accounts.roles.as_deref(), ctx.accounts.payer.key, Role::FeedManager)?;
 
     require!(
         args.max_bet_period >= args.min_bet_period && args.max_settle_period >= args.min_settle_period,
         MarketError::InvalidArgument
     );
+    require!(
+        args.min_creator_fee_bps <= args.max_creator_fee_bps && args.max_creator_fee_bps as u64 <= BPS_DENOMINATOR,
+        MarketError::InvalidArgument
+    );
+
+    let feed_id = args.provider.parse_feed_id(&args.feed_id)?;
 
     for pf in &state.allowed_pricefeeds {
-        if pf.symbol == args.symbol {
+        if pf.symbol == args.symbol || pf.feed_id == feed_id {
             return err!(MarketError::AccountAlreadyInitialized);
         }
     }
 
     let config = PriceFeedConfig {
         symbol: args.symbol.clone(),
-        pyth_feed_id: args.pyth_feed_id,
+        provider: args.provider,
+        feed_id,
         min_betting_period: args.min_bet_period,
         max_betting_period: args.max_bet_period,
         min_settling_period: args.min_settle_period,
         max_settling_period: args.max_settle_period,
         create_market_lamports: args.create_market_lamports,
         min_betting_lamports: args.min_betting_lamports,
+        min_creator_fee_bps: args.min_creator_fee_bps,
+        max_creator_fee_bps: args.max_creator_fee_bps,
+        creation_bond: args.creation_bond,
+        status: FeedStatus::Listed,
+        secondary_sources: parse_sources(&args.secondary_sources)?,
+        max_divergence_bps: args.max_divergence_bps,
+        min_verification: args.min_verification,
+        outage_grace_period: args.outage_grace_period,
+        breaker: args.breaker,
+        oracle_limits: args.oracle_limits,
     };
+    require!(
+        config.sources_valid() && config.breaker_valid() && config.oracle_limits.is_valid(),
+        MarketError::InvalidArgument
+    );
+    emit!(PriceFeedAdded {
+        symbol: config.symbol.clone(),
+        provider: config.provider,
+        feed_id: config.feed_id,
+        create_market_lamports: config.create_market_lamports,
+        min_betting_lamports: config.min_betting_lamports,
+        min_betting_period: config.min_betting_period,
+        max_betting_period: config.max_betting_period,
+        min_settling_period: config.min_settling_period,
+        max_settling_period: config.max_settling_period,
+        min_creator_fee_bps: config.min_creator_fee_bps,
+        max_creator_fee_bps: config.max_creator_fee_bps,
+        creation_bond: config.creation_bond,
+        secondary_sources: config.secondary_sources.clone(),
+        max_divergence_bps: config.max_divergence_bps,
+        min_verification: config.min_verification,
+        outage_grace_period: config.outage_grace_period,
+        breaker: config.breaker,
+        oracle_limits: config.oracle_limits,
+    });
     state.allowed_pricefeeds.push(config);
-
-    msg!("AddPriceFeed => symbol={}", args.symbol);
     Ok(())
 }
 
@@ -55,23 +119,149 @@ pub struct AddPriceFeed<'info> {
     #[account(
         mut,
         seeds = [STATE_SEED],
-        bump,
-        // constraint = state.admin_pubkey == payer.key() @ MarketError::IllegalOwner
+        bump
     )]
     pub state: Account<'info, State>,
 
+    #[account(
+        seeds = [ROLES_SEED],
+        bump
+    )]
+    pub roles: Option<Account<'info, Roles>>,
+
     pub system_program: Program<'info, System>,
 }
 
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct UpdatePriceFeedArgs {
+    symbol: String,
+    // fields left as None are kept as they are
+    min_bet_period: Option<u16>,
+    max_bet_period: Option<u16>,
+    min_settle_period: Option<u16>,
+    max_settle_period: Option<u16>,
+    create_market_lamports: Option<u64>,
+    min_betting_lamports: Option<u64>,
+    // replaces the whole list
+    secondary_sources: Option<Vec<OracleSourceArgs>>,
+    max_divergence_bps: Option<u16>,
+    min_verification: Option<PythVerification>,
+    outage_grace_period: Option<u32>,
+    breaker: Option<CircuitBreaker>,
+    oracle_limits: Option<OraclePolicy>,
+    // also clamp the periods, minimum bet and oracle policy of markets already on the feed
+    apply_to_existing: bool,
+}
+
+pub fn update_price_feed_impl(ctx: Context<UpdatePriceFeed>, args: UpdatePriceFeedArgs) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    state.require_role(ctx.accounts.roles.as_deref(), ctx.accounts.signer.key, Role::FeedManager)?;
+
+    let pf_opt = state.allowed_pricefeeds.iter_mut().find(|pf| pf.symbol == args.symbol);
+    require!(pf_opt.is_some(), MarketError::InvalidPriceFeed);
+    let pf = pf_opt.unwrap();
+
+    pf.min_betting_period = args.min_bet_period.unwrap_or(pf.min_betting_period);
+    pf.max_betting_period = args.max_bet_period.unwrap_or(pf.max_betting_period);
+    pf.min_settling_period = args.min_settle_period.unwrap_or(pf.min_settling_period);
+    pf.max_settling_period = args.max_settle_period.unwrap_or(pf.max_settling_period);
+    pf.create_market_lamports = args.create_market_lamports.unwrap_or(pf.create_market_lamports);
+    pf.min_betting_lamports = args.min_betting_lamports.unwrap_or(pf.min_betting_lamports);
+    if let Some(sources) = &args.secondary_sources {
+        pf.secondary_sources = parse_sources(sources)?;
+    }
+    pf.max_divergence_bps = args.max_divergence_bps.unwrap_or(pf.max_divergence_bps);
+    pf.min_verification = args.min_verification.unwrap_or(pf.min_verification);
+    pf.outage_grace_period = args.outage_grace_period.unwrap_or(pf.outage_grace_period);
+    pf.breaker = args.breaker.unwrap_or(pf.breaker);
+    pf.oracle_limits = args.oracle_limits.unwrap_or(pf.oracle_limits);
+    require!(
+        pf.max_betting_period >= pf.min_betting_period && pf.max_settling_period >= pf.min_settling_period,
+        MarketError::InvalidArgument
+    );
+    require!(
+        pf.sources_valid() && pf.breaker_valid() && pf.oracle_limits.is_valid(),
+        MarketError::InvalidArgument
+    );
+
+    let pf = pf.clone();
+    let mut markets_updated = 0u32;
+    if args.apply_to_existing {
+        for mk in state.markets.iter_mut().filter(|m| m.feed_id == pf.feed_id) {
+            mk.apply_feed_limits(&pf)?;
+            markets_updated += 1;
+        }
+    }
+
+    emit!(PriceFeedUpdated {
+        symbol: pf.symbol,
+        create_market_lamports: pf.create_market_lamports,
+        min_betting_lamports: pf.min_betting_lamports,
+        min_betting_period: pf.min_betting_period,
+        max_betting_period: pf.max_betting_period,
+        min_settling_period: pf.min_settling_period,
+        max_settling_period: pf.max_settling_period,
+        secondary_sources: pf.secondary_sources,
+        max_divergence_bps: pf.max_divergence_bps,
+        min_verification: pf.min_verification,
+        outage_grace_period: pf.outage_grace_period,
+        breaker: pf.breaker,
+        oracle_limits: pf.oracle_limits,
+        apply_to_existing: args.apply_to_existing,
+        markets_updated,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdatePriceFeed<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED],
+        bump
+    )]
+    pub state: Account<'info, State>,
+
+    #[account(
+        seeds = [ROLES_SEED],
+        bump
+    )]
+    pub roles: Option<Account<'info, Roles>>,
+}
+
+pub fn set_price_feed_status_impl(ctx: Context<UpdatePriceFeed>, symbol: String, status: FeedStatus) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    state.require_role(ctx.accounts.roles.as_deref(), ctx.accounts.signer.key, Role::FeedManager)?;
+
+    let pf_opt = state.allowed_pricefeeds.iter_mut().find(|pf| pf.symbol == symbol);
+    require!(pf_opt.is_some(), MarketError::InvalidPriceFeed);
+    let pf = pf_opt.unwrap();
+    require!(pf.status.can_become(status), MarketError::InvalidArgument);
+    pf.status = status;
+
+    emit!(PriceFeedStatusSet { symbol, status });
+    Ok(())
+}
+
 pub fn remove_price_feed_impl(ctx: Context<RemovePriceFeed>, symbol: String) -> Result<()> {
     let state = &mut ctx.accounts.state;
+    state.require_role(ctx.accounts.roles.as_deref(), ctx.accounts.signer.key, Role::FeedManager)?;
     let pos_opt = state
         .allowed_pricefeeds
         .iter()
         .position(|pf| pf.symbol == symbol);
     if let Some(idx) = pos_opt {
+        let pf = &state.allowed_pricefeeds[idx];
+        require!(pf.status == FeedStatus::Retired, MarketError::InvalidPriceFeed);
+        // markets on this feed must have settled every round they locked
+        require!(
+            !state.markets.iter().any(|m| m.feed_id == pf.feed_id && m.open_rounds > 0),
+            MarketError::RoundInProgress
+        );
         state.allowed_pricefeeds.remove(idx);
-        msg!("RemovePriceFeed => symbol={}", symbol);
+        emit!(PriceFeedRemoved { symbol });
         Ok(())
     } else {
         err!(MarketError::InvalidArgument)
@@ -82,13 +272,111 @@ pub fn remove_price_feed_impl(ctx: Context<RemovePriceFeed>, symbol: String) ->
 #[derive(Accounts)]
 pub struct RemovePriceFeed<'info> {
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub signer: Signer<'info>,
 
     #[account(
         mut,
         seeds = [STATE_SEED],
-        bump,
-        constraint = state.admin_pubkey == admin.key() @ MarketError::IllegalOwner
+        bump
     )]
     pub state: Account<'info, State>,
+
+    #[account(
+        seeds = [ROLES_SEED],
+        bump
+    )]
+    pub roles: Option<Account<'info, Roles>>,
+}
+
+pub fn attest_price_impl(ctx: Context<AttestPrice>, feed_id: [u8; 32], price: u64, conf: u64) -> Result<()> {
+    let signer = &ctx.accounts.signer;
+    let state = &ctx.accounts.state;
+    state.require_role(ctx.accounts.roles.as_deref(), signer.key, Role::Attester)?;
+    require!(
+        state.allowed_pricefeeds.iter().any(|pf| pf.feed_id == feed_id && pf.provider == OracleProvider::Manual),
+        MarketError::InvalidPriceFeed
+    );
+    require!(price > 0, MarketError::InvalidOraclePrice);
+
+    let attested = &mut ctx.accounts.attested_price;
+    attested.feed_id = feed_id;
+    attested.price = price;
+    attested.conf = conf;
+    attested.publish_time = Clock::get()?.unix_timestamp;
+    attested.attester = signer.key();
+
+    emit!(PriceAttested {
+        feed_id,
+        price,
+        conf,
+        publish_time: attested.publish_time,
+        attester: attested.attester,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(feed_id: [u8; 32])]
+pub struct AttestPrice<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [STATE_SEED],
+        bump
+    )]
+    pub state: Account<'info, State>,
+
+    #[account(
+        seeds = [ROLES_SEED],
+        bump
+    )]
+    pub roles: Option<Account<'info, Roles>>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = ANCHOR_DISCRIMINATOR + AttestedPrice::INIT_SPACE,
+        seeds = [ATTESTED_PRICE_SEED, feed_id.as_ref()],
+        bump
+    )]
+    pub attested_price: Account<'info, AttestedPrice>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Writes the price every oracle read returns.
+#[cfg(feature = "test-oracle")]
+pub fn set_mock_price_impl(
+    ctx: Context<SetMockPrice>,
+    feed_id: [u8; 32],
+    price: u64,
+    conf: u64,
+    publish_time: i64,
+) -> Result<()> {
+    let mock = &mut ctx.accounts.mock_price;
+    mock.feed_id = feed_id;
+    mock.price = price;
+    mock.conf = conf;
+    mock.publish_time = publish_time;
+    Ok(())
+}
+
+#[cfg(feature = "test-oracle")]
+#[derive(Accounts)]
+#[instruction(feed_id: [u8; 32])]
+pub struct SetMockPrice<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = ANCHOR_DISCRIMINATOR + MockPrice::INIT_SPACE,
+        seeds = [MOCK_PRICE_SEED, feed_id.as_ref()],
+        bump
+    )]
+    pub mock_price: Account<'info, MockPrice>,
+
+    pub system_program: Program<'info, System>,
 }
@@ -1,2 +1,192 @@
 use anchor_lang::prelude::*;
 
+use crate::error::MarketError;
+use crate::constants::*;
+use crate::state::*;
+
+// Read-only instructions answered through return data, meant to be simulated.
+
+#[derive(AnchorSerialize, AnchorDeserialize, PartialEq, Debug, Clone, Copy)]
+pub enum RoundPhase {
+    // not opened by process_round yet
+    Pending,
+    Betting,
+    Locked,
+    Settleable,
+    Settled,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, PartialEq, Debug, Clone)]
+pub struct RoundStatus {
+    pub round_index: u32,
+    pub phase: RoundPhase,
+    pub start_time: u32,
+    pub betting_end_time: u32,
+    pub end_time: u32,
+    pub start_price: u64,
+    pub end_price: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, PartialEq, Debug, Clone)]
+pub struct RoundPools {
+    pub total_up: u64,
+    pub total_down: u64,
+    pub bet_count: u32,
+}
+
+/// Gross payout per unit staked if that side wins, after fees, in basis points.
+/// Zero when nobody has bet on that side yet.
+#[derive(AnchorSerialize, AnchorDeserialize, PartialEq, Debug, Clone)]
+pub struct ImpliedOdds {
+    pub up_payout_bps: u64,
+    pub down_payout_bps: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, PartialEq, Debug, Clone)]
+pub struct UserPosition {
+    pub user: Pubkey,
+    pub up_amount: u64,
+    pub down_amount: u64,
+    // settled payout owed to the user, zero before settlement
+    pub result: u64,
+    pub paid_out: u64,
+}
+
+fn find_market<'a>(state: &'a State, market_id: &str) -> Result<&'a Market> {
+    let mk_opt = state.markets.iter().find(|m| m.market_id == market_id);
+    require!(mk_opt.is_some(), MarketError::InvalidMarket);
+    Ok(mk_opt.unwrap())
+}
+
+fn payout_bps(side: u64, other: u64, fee_rate_bps: u16) -> u64 {
+    if side == 0 {
+        return 0;
+    }
+    let distributable = other.saturating_sub(bps_of(other, fee_rate_bps));
+    BPS_DENOMINATOR + (distributable as u128 * BPS_DENOMINATOR as u128 / side as u128) as u64
+}
+
+// --------------------------
+//    QueryMarket
+// --------------------------
+pub fn query_market_impl(ctx: Context<QueryMarket>, market_id: String) -> Result<Market> {
+    Ok(find_market(&ctx.accounts.state, &market_id)?.clone())
+}
+
+#[derive(Accounts)]
+pub struct QueryMarket<'info> {
+    #[account(
+        seeds = [STATE_SEED],
+        bump
+    )]
+    pub state: Account<'info, State>,
+}
+
+// --------------------------
+//    QueryRoundStatus
+// --------------------------
+pub fn query_round_status_impl(ctx: Context<QueryRound>, market_id: String, round_index: u32) -> Result<RoundStatus> {
+    find_market(&ctx.accounts.state, &market_id)?;
+    let round = &ctx.accounts.round;
+    require!(round.market_id == market_id, MarketError::InvalidMarket);
+
+    let now = Clock::get()?.unix_timestamp as u32;
+    let betting_end_time = round.betting_end;
+    let phase = if round.settled {
+        RoundPhase::Settled
+    } else if round.start_time == 0 {
+        RoundPhase::Pending
+    } else if now <= betting_end_time {
+        RoundPhase::Betting
+    } else if now < round.end_time {
+        RoundPhase::Locked
+    } else {
+        RoundPhase::Settleable
+    };
+
+    Ok(RoundStatus {
+        round_index,
+        phase,
+        start_time: round.start_time,
+        betting_end_time,
+        end_time: round.end_time,
+        start_price: round.start_price,
+        end_price: round.end_price,
+    })
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: String, round_index: u32)]
+pub struct QueryRound<'info> {
+    #[account(
+        seeds = [STATE_SEED],
+        bump
+    )]
+    pub state: Account<'info, State>,
+
+    #[account(
+        seeds = [ROUND_SEED, round_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub round: Account<'info, Round>,
+}
+
+// --------------------------
+//    QueryPools
+// --------------------------
+pub fn query_pools_impl(ctx: Context<QueryRound>, market_id: String, _round_index: u32) -> Result<RoundPools> {
+    let round = &ctx.accounts.round;
+    require!(round.market_id == market_id, MarketError::InvalidMarket);
+
+    Ok(RoundPools {
+        total_up: round.total_up,
+        total_down: round.total_down,
+        bet_count: round.bets.len() as u32,
+    })
+}
+
+// --------------------------
+//    QueryImpliedOdds
+// --------------------------
+pub fn query_implied_odds_impl(ctx: Context<QueryRound>, market_id: String, _round_index: u32) -> Result<ImpliedOdds> {
+    let mk = find_market(&ctx.accounts.state, &market_id)?;
+    let round = &ctx.accounts.round;
+    require!(round.market_id == market_id, MarketError::InvalidMarket);
+
+    Ok(ImpliedOdds {
+        up_payout_bps: payout_bps(round.total_up, round.total_down, mk.fee_rate_bps),
+        down_payout_bps: payout_bps(round.total_down, round.total_up, mk.fee_rate_bps),
+    })
+}
+
+// --------------------------
+//    QueryUserPosition
+// --------------------------
+pub fn query_user_position_impl(
+    ctx: Context<QueryRound>,
+    market_id: String,
+    _round_index: u32,
+    user: Pubkey,
+) -> Result<UserPosition> {
+    let round = &ctx.accounts.round;
+    require!(round.market_id == market_id, MarketError::InvalidMarket);
+
+    let mut position = UserPosition {
+        user,
+        up_amount: 0,
+        down_amount: 0,
+        result: 0,
+        paid_out: 0,
+    };
+    for bet in round.bets.iter().filter(|b| b.user == user) {
+        match bet.direction {
+            Direction::Up => position.up_amount = position.up_amount.saturating_add(bet.amount),
+            Direction::Down => position.down_amount = position.down_amount.saturating_add(bet.amount),
+        }
+        position.result = position.result.saturating_add(bet.result);
+        if bet.refunded {
+            position.paid_out = position.paid_out.saturating_add(bet.result);
+        }
+    }
+    Ok(position)
+}
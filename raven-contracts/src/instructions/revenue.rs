@@ -0,0 +1,274 @@
+use anchor_lang::{prelude::*, solana_program::{program::invoke_signed, system_instruction}};
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount};
+
+use crate::error::MarketError;
+use crate::events::*;
+use crate::constants::*;
+use crate::state::*;
+
+// --------------------------
+//    SetRevenueConfig
+// --------------------------
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct SetRevenueConfigArgs {
+    operations_bps: u16,
+    buyback_bps: u16,
+    staking_bps: u16,
+    rm_mint: Pubkey,
+}
+
+pub fn set_revenue_config_impl(ctx: Context<SetRevenueConfig>, args: SetRevenueConfigArgs) -> Result<()> {
+    let split = RevenueSplit {
+        operations_bps: args.operations_bps,
+        buyback_bps: args.buyback_bps,
+        staking_bps: args.staking_bps,
+    };
+    require!(split.is_valid(), MarketError::InvalidArgument);
+    ctx.accounts.state.require_role(ctx.accounts.roles.as_deref(), ctx.accounts.signer.key, Role::Treasurer)?;
+
+    // a vault below its rent-exempt reserve cannot take a small fee share, so the
+    // treasurer funds the reserve of every vault the split starts paying into
+    let reserve = Rent::get()?.minimum_balance(0);
+    for (vault, bps) in [
+        (&ctx.accounts.buyback_vault, split.buyback_bps),
+        (&ctx.accounts.staking_vault, split.staking_bps),
+    ] {
+        let top_up = reserve.saturating_sub(vault.lamports());
+        if bps > 0 && top_up > 0 {
+            invoke_signed(
+                &system_instruction::transfer(ctx.accounts.signer.key, vault.key, top_up),
+                &[
+                    ctx.accounts.signer.to_account_info(),
+                    vault.clone(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+                &[],
+            )?;
+        }
+    }
+
+    let state = &mut ctx.accounts.state;
+    state.revenue_split = split;
+    state.rm_mint = args.rm_mint;
+
+    emit!(RevenueConfigSet {
+        operations_bps: args.operations_bps,
+        buyback_bps: args.buyback_bps,
+        staking_bps: args.staking_bps,
+        rm_mint: args.rm_mint,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetRevenueConfig<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED],
+        bump
+    )]
+    pub state: Account<'info, State>,
+
+    #[account(
+        seeds = [ROLES_SEED],
+        bump
+    )]
+    pub roles: Option<Account<'info, Roles>>,
+
+    /// CHECK: buyback_vault
+    #[account(
+        mut,
+        seeds = [BUYBACK_SEED],
+        bump
+    )]
+    pub buyback_vault: AccountInfo<'info>,
+
+    /// CHECK: staking_vault
+    #[account(
+        mut,
+        seeds = [STAKING_SEED],
+        bump
+    )]
+    pub staking_vault: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// --------------------------
+//    BurnBuyback
+// --------------------------
+
+pub fn burn_buyback_impl(ctx: Context<BurnBuyback>) -> Result<()> {
+    let amount = ctx.accounts.buyback_token_account.amount;
+    require!(amount > 0, MarketError::InvalidArgument);
+
+    let seeds: &[&[u8]] = &[BUYBACK_SEED, &[ctx.bumps.buyback_vault]];
+    token::burn(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.rm_mint.to_account_info(),
+                from: ctx.accounts.buyback_token_account.to_account_info(),
+                authority: ctx.accounts.buyback_vault.to_account_info(),
+            },
+            &[seeds],
+        ),
+        amount,
+    )?;
+
+    let state = &mut ctx.accounts.state;
+    state.total_rm_burned = state.total_rm_burned.saturating_add(amount);
+
+    emit!(BuybackBurned {
+        amount,
+        total_burned: state.total_rm_burned,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct BurnBuyback<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED],
+        bump
+    )]
+    pub state: Account<'info, State>,
+
+    /// CHECK: buyback vault, authority of the buyback token account
+    #[account(
+        seeds = [BUYBACK_SEED],
+        bump
+    )]
+    pub buyback_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        address = state.rm_mint @ MarketError::InvalidArgument
+    )]
+    pub rm_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = rm_mint,
+        token::authority = buyback_vault
+    )]
+    pub buyback_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+// --------------------------
+//    WithdrawRevenue
+// --------------------------
+
+/// Moves SOL collected in the buyback or staking vault to `recipient`, e.g. the desk that
+/// swaps it for $RM or the staking rewards program. The vault keeps its rent-exempt reserve.
+pub fn withdraw_revenue_impl(ctx: Context<WithdrawRevenue>, vault: RevenueVault, amount: u64) -> Result<()> {
+    ctx.accounts.state.require_role(ctx.accounts.roles.as_deref(), ctx.accounts.signer.key, Role::Treasurer)?;
+    require!(amount > 0, MarketError::InvalidArgument);
+
+    let revenue_vault = &ctx.accounts.revenue_vault;
+    let reserve = Rent::get()?.minimum_balance(0);
+    require!(
+        revenue_vault.lamports().saturating_sub(reserve) >= amount,
+        MarketError::InvalidArgument
+    );
+
+    let seeds: &[&[u8]] = &[vault.seed(), &[ctx.bumps.revenue_vault]];
+    invoke_signed(
+        &system_instruction::transfer(revenue_vault.key, ctx.accounts.recipient.key, amount),
+        &[
+            revenue_vault.clone(),
+            ctx.accounts.recipient.clone(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+        &[seeds],
+    )?;
+
+    emit!(RevenueWithdrawn {
+        vault,
+        amount,
+        recipient: ctx.accounts.recipient.key(),
+        by: ctx.accounts.signer.key(),
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(vault: RevenueVault)]
+pub struct WithdrawRevenue<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [STATE_SEED],
+        bump
+    )]
+    pub state: Account<'info, State>,
+
+    #[account(
+        seeds = [ROLES_SEED],
+        bump
+    )]
+    pub roles: Option<Account<'info, Roles>>,
+
+    /// CHECK: buyback or staking vault, as selected by `vault`
+    #[account(
+        mut,
+        seeds = [vault.seed()],
+        bump
+    )]
+    pub revenue_vault: AccountInfo<'info>,
+
+    /// CHECK: any wallet or program account chosen by the treasurer
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// --------------------------
+//    revenue distribution
+// --------------------------
+
+pub struct RevenueAccounts<'a, 'info> {
+    pub operations: &'a AccountInfo<'info>,
+    pub buyback: &'a AccountInfo<'info>,
+    pub staking: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+/// Splits a protocol fee according to `split` and transfers each share out of `from`.
+/// `signer_seeds` is empty when `from` is a wallet that signed the transaction.
+pub fn distribute_revenue<'info>(
+    split: &RevenueSplit,
+    amount: u64,
+    from: &AccountInfo<'info>,
+    to: &RevenueAccounts<'_, 'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let (operations, buyback, staking) = split.split(amount);
+    for (dest, lamports) in [(to.operations, operations), (to.buyback, buyback), (to.staking, staking)] {
+        if lamports == 0 {
+            continue;
+        }
+        invoke_signed(
+            &system_instruction::transfer(from.key, dest.key, lamports),
+            &[from.clone(), dest.clone(), to.system_program.clone()],
+            signer_seeds,
+        )?;
+    }
+    emit!(RevenueDistributed {
+        operations,
+        buyback,
+        staking,
+    });
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+
+use crate::error::MarketError;
+use crate::events::*;
+use crate::constants::*;
+use crate::state::*;
+
+// --------------------------
+//    CheckSolvency
+// --------------------------
+pub fn check_solvency_impl(ctx: Context<CheckSolvency>, market_id: String) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    // the rent-exempt reserve can never be paid out
+    let vault_balance = ctx.accounts.escrow.lamports().saturating_sub(Rent::get()?.minimum_balance(0));
+
+    let mk_opt = state.markets.iter().find(|m| m.market_id == market_id);
+    require!(mk_opt.is_some(), MarketError::InvalidMarket);
+    let mk = mk_opt.unwrap();
+    require!(!mk.legacy_escrow, MarketError::VaultNotMigrated);
+
+    let open_stakes = mk.open_stakes;
+    let unpaid_winnings = mk.unpaid_winnings;
+    let accrued_fees = mk.accrued_creator_fees();
+    let bonds = mk.bond_lamports;
+    let liabilities = mk.liabilities();
+
+    let solvent = vault_balance >= liabilities;
+    if !solvent {
+        state.paused = true;
+        msg!("CheckSolvency => market={}, shortfall={}, protocol paused", market_id, liabilities - vault_balance);
+    }
+
+    emit!(SolvencyChecked {
+        market_id,
+        vault_balance,
+        open_stakes,
+        unpaid_winnings,
+        accrued_fees,
+        bonds,
+        liabilities,
+        solvent,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(market_id: String)]
+pub struct CheckSolvency<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED],
+        bump
+    )]
+    pub state: Account<'info, State>,
+
+    /// CHECK: market escrow vault
+    #[account(
+        seeds = [ESCROW_SEED, market_id.as_bytes()],
+        bump
+    )]
+    pub escrow: AccountInfo<'info>,
+}
+
+// --------------------------
+//    PauseProtocol
+// --------------------------
+/// Emergency stop for every market at once. Refunds, claims and settlement of running
+/// rounds keep working; only the admin can resume.
+pub fn pause_protocol_impl(ctx: Context<PauseProtocol>) -> Result<()> {
+    ctx.accounts.state.paused = true;
+    emit!(ProtocolPaused {
+        by: ctx.accounts.signer.key(),
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct PauseProtocol<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED],
+        bump,
+        constraint = (state.guardian == signer.key() || state.admin_pubkey == signer.key()) @ MarketError::IllegalOwner
+    )]
+    pub state: Account<'info, State>,
+}
+
+// --------------------------
+//    ResumeProtocol
+// --------------------------
+pub fn resume_protocol_impl(ctx: Context<ResumeProtocol>) -> Result<()> {
+    ctx.accounts.state.paused = false;
+    emit!(ProtocolResumed {
+        admin: ctx.accounts.admin.key(),
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ResumeProtocol<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [STATE_SEED],
+        bump,
+        constraint = state.admin_pubkey == admin.key() @ MarketError::IllegalOwner
+    )]
+    pub state: Account<'info, State>,
+}
@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use pyth_solana_receiver_sdk::price_update::get_feed_id_from_hex;
+
+use crate::constants::*;
+use crate::error::MarketError;
+use crate::state::*;
+
+// Account layouts as deployed before fees moved to basis points and feed ids to bytes.
+// Only read by `migrate_state`; never written.
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct LegacyState {
+    pub admin_pubkey: Pubkey,
+    pub escrow_pubkey: Pubkey,
+    pub escrow_bump: u8,
+    pub creator_fee_percent: u8,
+
+    pub allowed_pricefeeds: Vec<LegacyPriceFeedConfig>,
+    pub markets: Vec<LegacyMarket>,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct LegacyPriceFeedConfig {
+    pub symbol: String,
+    pub pyth_feed_id: String,
+    pub create_market_lamports: u64,
+    pub min_betting_lamports: u64,
+    pub min_betting_period: u16,
+    pub max_betting_period: u16,
+    pub min_settling_period: u16,
+    pub max_settling_period: u16,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct LegacyMarket {
+    pub market_id: String,
+    pub pyth_feed_id: String,
+    pub creation_time: u32,
+    pub paused: bool,
+    pub fee_rate: u8,
+    pub min_betting_price: u64,
+    pub betting_period: u16,
+    pub settling_period: u16,
+    pub creator_pubkey: Pubkey,
+
+    pub round_index: u32,
+}
+
+fn percent_to_bps(percent: u8) -> u16 {
+    percent as u16 * 100
+}
+
+fn parse_feed_id(hex: &str) -> Result<[u8; 32]> {
+    Ok(get_feed_id_from_hex(hex).map_err(|_| MarketError::InvalidPriceFeed)?)
+}
+
+impl LegacyState {
+    /// Fails if any stored feed id is not valid hex; such a feed could never have settled. Also
+    /// fails on market ids too long to seed a per-market escrow vault, whose stakes could
+    /// otherwise never leave the global one.
+    pub fn into_current(self) -> Result<State> {
+        let creator_fee_bps = percent_to_bps(self.creator_fee_percent);
+        Ok(State {
+            admin_pubkey: self.admin_pubkey,
+            pending_admin: None,
+            escrow_pubkey: self.escrow_pubkey,
+            escrow_bump: self.escrow_bump,
+            creator_fee_bps,
+            revenue_split: RevenueSplit::default(),
+            rm_mint: Pubkey::default(),
+            total_rm_burned: 0,
+            paused: false,
+            guardian: Pubkey::default(),
+            allowed_pricefeeds: self.allowed_pricefeeds.into_iter().map(|pf| pf.into_current()).collect::<Result<_>>()?,
+            markets: self.markets.into_iter().map(|m| m.into_current(creator_fee_bps)).collect::<Result<_>>()?,
+        })
+    }
+}
+
+impl LegacyPriceFeedConfig {
+    pub fn into_current(self) -> Result<PriceFeedConfig> {
+        Ok(PriceFeedConfig {
+            symbol: self.symbol,
+            provider: OracleProvider::PythPull,
+            feed_id: parse_feed_id(&self.pyth_feed_id)?,
+            create_market_lamports: self.create_market_lamports,
+            min_betting_lamports: self.min_betting_lamports,
+            min_betting_period: self.min_betting_period,
+            max_betting_period: self.max_betting_period,
+            min_settling_period: self.min_settling_period,
+            max_settling_period: self.max_settling_period,
+            // legacy feeds had no bounds on the creator split
+            min_creator_fee_bps: 0,
+            max_creator_fee_bps: BPS_DENOMINATOR as u16,
+            creation_bond: false,
+            status: FeedStatus::Listed,
+            secondary_sources: vec![],
+            max_divergence_bps: 0,
+            min_verification: PythVerification::Full,
+            outage_grace_period: 0,
+            breaker: CircuitBreaker::default(),
+            oracle_limits: OraclePolicy::LEGACY,
+        })
+    }
+}
+
+impl LegacyMarket {
+    pub fn into_current(self, creator_fee_bps: u16) -> Result<Market> {
+        require!(self.market_id.len() <= MAX_MARKET_ID_LEN, MarketError::MarketIdTooLong);
+        Ok(Market {
+            market_id: self.market_id,
+            provider: OracleProvider::PythPull,
+            feed_id: parse_feed_id(&self.pyth_feed_id)?,
+            creation_time: self.creation_time,
+            paused: self.paused,
+            fee_rate_bps: percent_to_bps(self.fee_rate),
+            min_betting_price: self.min_betting_price,
+            betting_period: self.betting_period,
+            settling_period: self.settling_period,
+            creator_pubkey: self.creator_pubkey,
+            creator_fee_recipient: self.creator_pubkey,
+            creator_fees_accrued: 0,
+            // legacy markets used the global split
+            creator_fee_bps,
+            creator_weight: 1,
+            co_creators: vec![],
+            bond_lamports: 0,
+            // stakes placed before the ledger existed are not tracked
+            open_stakes: 0,
+            unpaid_winnings: 0,
+            // locked legacy rounds are voided by migrate_round
+            open_rounds: 0,
+            legacy_escrow: true,
+            settlement: SettlementMode::Spot,
+            awaiting_price: false,
+            oracle_policy: OraclePolicy::LEGACY,
+            round_index: self.round_index,
+        })
+    }
+}
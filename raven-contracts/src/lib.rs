@@ -4,7 +4,10 @@ use anchor_lang::solana_program::sysvar::Sysvar as SolanaSysvar;
 pub mod error;
 pub mod state;
 pub mod constants;
+pub mod events;
 pub mod instructions;
+pub mod legacy;
+pub mod oracle;
 
 pub use crate::constants::*;
 pub use crate::state::*;
@@ -13,88 +16,330 @@ pub use instructions::init_state::*;
 pub use instructions::price_feed::*;
 pub use instructions::market::*;
 pub use instructions::bet::*;
+pub use instructions::revenue::*;
+pub use instructions::migrate::*;
+pub use instructions::solvency::*;
+pub use instructions::query::*;
+pub use instructions::admin::*;
 
 declare_id!("21fdwXkLrfsh1H8tto8fYKmJ3sfJP9W258YTb7J9wbFT");
 
-#[program]
-pub mod raven_market {
-    use instructions::market::{create_market_impl, pause_market_impl, resume_market_impl};
+// Anchor 0.30 ignores #[cfg] on instructions, so the program module is generated by a macro
+// and test-only instructions are passed in when their feature is on.
+macro_rules! raven_program {
+    ($($test_only:item)*) => {
+        #[program]
+        pub mod raven_market {
+            use instructions::market::{
+                claim_creator_fees_impl, create_market_impl, pause_market_impl, resume_market_impl,
+                retire_market_impl, set_creator_fee_recipient_impl, slash_market_bond_impl,
+            };
 
-    use super::*;
+            use super::*;
 
-    // --------------------------
-    //    4.1 InitState
-    // --------------------------
-    pub fn init_state(ctx: Context<InitState>, admin_pubkey: Pubkey, creator_fee_percent: u8) -> Result<()> {
-        init_state_impl(ctx, admin_pubkey, creator_fee_percent)
-    }
+            // --------------------------
+            //    4.1 InitState
+            // --------------------------
+            pub fn init_state(ctx: Context<InitState>, admin_pubkey: Pubkey, creator_fee_bps: u16) -> Result<()> {
+                init_state_impl(ctx, admin_pubkey, creator_fee_bps)
+            }
 
-    // --------------------------
-    //    4.2 AddPriceFeed
-    // --------------------------
-    pub fn add_price_feed(ctx: Context<AddPriceFeed>, args: AddPriceFeedArgs) -> Result<()> {
-        add_price_feed_impl(ctx, args)
-    }
+            // --------------------------
+            //    4.2 AddPriceFeed
+            // --------------------------
+            pub fn add_price_feed(ctx: Context<AddPriceFeed>, args: AddPriceFeedArgs) -> Result<()> {
+                add_price_feed_impl(ctx, args)
+            }
 
-    // --------------------------
-    //    4.3 RemovePriceFeed
-    // --------------------------
-    pub fn remove_price_feed(ctx: Context<RemovePriceFeed>, symbol: String) -> Result<()> {
-        remove_price_feed_impl(ctx, symbol)
-    }
+            // --------------------------
+            //    4.3 RemovePriceFeed
+            // --------------------------
+            pub fn remove_price_feed(ctx: Context<RemovePriceFeed>, symbol: String) -> Result<()> {
+                remove_price_feed_impl(ctx, symbol)
+            }
 
-    // --------------------------
-    //    4.4 CreateMarket
-    // --------------------------
-    pub fn create_market(ctx: Context<CreateMarket>, args: CreateMarketArgs) -> Result<()> {
-        create_market_impl(ctx, args)
-    }
+            // --------------------------
+            //    4.4 CreateMarket
+            // --------------------------
+            pub fn create_market(ctx: Context<CreateMarket>, args: CreateMarketArgs) -> Result<()> {
+                create_market_impl(ctx, args)
+            }
 
-    // --------------------------
-    //    4.5 PauseMarket
-    // --------------------------
-    pub fn pause_market(ctx: Context<PauseMarket>, market_id: String) -> Result<()> {
-        pause_market_impl(ctx, market_id)
-    }
+            // --------------------------
+            //    4.5 PauseMarket
+            // --------------------------
+            pub fn pause_market(ctx: Context<PauseMarket>, market_id: String) -> Result<()> {
+                pause_market_impl(ctx, market_id)
+            }
 
-    // --------------------------
-    //    4.6 ResumeMarket
-    // --------------------------
-    pub fn resume_market(ctx: Context<ResumeMarket>, market_id: String) -> Result<()> {
-        resume_market_impl(ctx, market_id)
-    }
+            // --------------------------
+            //    4.6 ResumeMarket
+            // --------------------------
+            pub fn resume_market(ctx: Context<ResumeMarket>, market_id: String) -> Result<()> {
+                resume_market_impl(ctx, market_id)
+            }
 
-    // --------------------------
-    //    4.7 UserBet
-    // --------------------------
-    pub fn user_bet(ctx: Context<UserBet>, args: UserBetArgs) -> Result<()> {
-        user_bet_impl(ctx, args)
-    }
+            // --------------------------
+            //    4.7 UserBet
+            // --------------------------
+            pub fn user_bet(ctx: Context<UserBet>, args: UserBetArgs) -> Result<()> {
+                user_bet_impl(ctx, args)
+            }
 
-    // --------------------------
-    //    4.8 AutoSettleAll
-    // --------------------------
-    pub fn process_round(ctx: Context<ProcessRound>, args: ProcessRoundArgs) -> Result<()> {
-        process_round_impl(ctx, args)
-    }
+            // --------------------------
+            //    4.8 AutoSettleAll
+            // --------------------------
+            pub fn process_round<'info>(
+                ctx: Context<'_, '_, 'info, 'info, ProcessRound<'info>>,
+                args: ProcessRoundArgs,
+            ) -> Result<()> {
+                process_round_impl(ctx, args)
+            }
 
-    // --------------------------
-    //    4.9 AutoSettleAll
-    // --------------------------
-    pub fn refund_round<'info>(
-        ctx: Context<'_, '_, '_, 'info, RefundRound<'info>>,
-        market_id: String,
-        round_index: u32,
-    ) -> Result<()> {
-        refund_round_impl(ctx, market_id, round_index)
-    }
+            // --------------------------
+            //    4.9 AutoSettleAll
+            // --------------------------
+            pub fn refund_round<'info>(
+                ctx: Context<'_, '_, 'info, 'info, RefundRound<'info>>,
+                market_id: String,
+                round_index: u32,
+            ) -> Result<()> {
+                refund_round_impl(ctx, market_id, round_index)
+            }
+
+            // --------------------------
+            //    4.9 claim_round
+            // --------------------------
+            pub fn claim_round(ctx: Context<ClaimRound>, market_id: String, round_index: u32) -> Result<()> {
+                claim_round_impl(ctx, market_id, round_index)
+            }
+
+            // --------------------------
+            //    4.9 close_round
+            // --------------------------
+            pub fn close_round(
+                ctx: Context<CloseRound>,
+                round_index: u32,
+            ) -> Result<()> {
+                close_round_impl(ctx, round_index)
+            }
+
+            // --------------------------
+            //    4.10 SetRevenueConfig
+            // --------------------------
+            pub fn set_revenue_config(ctx: Context<SetRevenueConfig>, args: SetRevenueConfigArgs) -> Result<()> {
+                set_revenue_config_impl(ctx, args)
+            }
+
+            // --------------------------
+            //    4.11 BurnBuyback
+            // --------------------------
+            pub fn burn_buyback(ctx: Context<BurnBuyback>) -> Result<()> {
+                burn_buyback_impl(ctx)
+            }
+
+            // --------------------------
+            //    4.12 SetCreatorFeeRecipient
+            // --------------------------
+            pub fn set_creator_fee_recipient(
+                ctx: Context<SetCreatorFeeRecipient>,
+                market_id: String,
+                recipient: Pubkey,
+            ) -> Result<()> {
+                set_creator_fee_recipient_impl(ctx, market_id, recipient)
+            }
+
+            // --------------------------
+            //    4.13 ClaimCreatorFees
+            // --------------------------
+            pub fn claim_creator_fees(ctx: Context<ClaimCreatorFees>, market_id: String) -> Result<()> {
+                claim_creator_fees_impl(ctx, market_id)
+            }
+
+            // --------------------------
+            //    4.14 MigrateState
+            // --------------------------
+            pub fn migrate_state(ctx: Context<MigrateState>) -> Result<()> {
+                migrate_state_impl(ctx)
+            }
+
+            // --------------------------
+            //    4.15 RetireMarket
+            // --------------------------
+            pub fn retire_market<'info>(
+                ctx: Context<'_, '_, 'info, 'info, RetireMarket<'info>>,
+                market_id: String,
+            ) -> Result<()> {
+                retire_market_impl(ctx, market_id)
+            }
+
+            // --------------------------
+            //    4.16 SlashMarketBond
+            // --------------------------
+            pub fn slash_market_bond(ctx: Context<SlashMarketBond>, market_id: String) -> Result<()> {
+                slash_market_bond_impl(ctx, market_id)
+            }
+
+            // --------------------------
+            //    4.17 CheckSolvency
+            // --------------------------
+            pub fn check_solvency(ctx: Context<CheckSolvency>, market_id: String) -> Result<()> {
+                check_solvency_impl(ctx, market_id)
+            }
+
+            // --------------------------
+            //    4.18 ResumeProtocol
+            // --------------------------
+            pub fn resume_protocol(ctx: Context<ResumeProtocol>) -> Result<()> {
+                resume_protocol_impl(ctx)
+            }
+
+            // --------------------------
+            //    4.19 MigrateMarketVault
+            // --------------------------
+            pub fn migrate_market_vault(ctx: Context<MigrateMarketVault>, market_id: String) -> Result<()> {
+                migrate_market_vault_impl(ctx, market_id)
+            }
+
+            // --------------------------
+            //    4.20 query_*
+            // --------------------------
+            pub fn query_market(ctx: Context<QueryMarket>, market_id: String) -> Result<Market> {
+                query_market_impl(ctx, market_id)
+            }
+
+            pub fn query_round_status(ctx: Context<QueryRound>, market_id: String, round_index: u32) -> Result<RoundStatus> {
+                query_round_status_impl(ctx, market_id, round_index)
+            }
+
+            pub fn query_pools(ctx: Context<QueryRound>, market_id: String, round_index: u32) -> Result<RoundPools> {
+                query_pools_impl(ctx, market_id, round_index)
+            }
+
+            pub fn query_implied_odds(ctx: Context<QueryRound>, market_id: String, round_index: u32) -> Result<ImpliedOdds> {
+                query_implied_odds_impl(ctx, market_id, round_index)
+            }
+
+            pub fn query_user_position(
+                ctx: Context<QueryRound>,
+                market_id: String,
+                round_index: u32,
+                user: Pubkey,
+            ) -> Result<UserPosition> {
+                query_user_position_impl(ctx, market_id, round_index, user)
+            }
+
+            // --------------------------
+            //    4.21 ProposeAdmin
+            // --------------------------
+            pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Option<Pubkey>) -> Result<()> {
+                propose_admin_impl(ctx, new_admin)
+            }
+
+            // --------------------------
+            //    4.22 AcceptAdmin
+            // --------------------------
+            pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+                accept_admin_impl(ctx)
+            }
+
+            // --------------------------
+            //    4.23 UpdateStateConfig
+            // --------------------------
+            pub fn update_state_config(ctx: Context<UpdateStateConfig>, args: UpdateStateConfigArgs) -> Result<()> {
+                update_state_config_impl(ctx, args)
+            }
+
+            // --------------------------
+            //    4.24 GrantRole
+            // --------------------------
+            pub fn grant_role(ctx: Context<GrantRole>, key: Pubkey, role: Role) -> Result<()> {
+                grant_role_impl(ctx, key, role)
+            }
+
+            // --------------------------
+            //    4.25 RevokeRole
+            // --------------------------
+            pub fn revoke_role(ctx: Context<RevokeRole>, key: Pubkey, role: Role) -> Result<()> {
+                revoke_role_impl(ctx, key, role)
+            }
+
+            // --------------------------
+            //    4.26 PauseProtocol
+            // --------------------------
+            pub fn pause_protocol(ctx: Context<PauseProtocol>) -> Result<()> {
+                pause_protocol_impl(ctx)
+            }
+
+            // --------------------------
+            //    4.27 UpdatePriceFeed
+            // --------------------------
+            pub fn update_price_feed(ctx: Context<UpdatePriceFeed>, args: UpdatePriceFeedArgs) -> Result<()> {
+                update_price_feed_impl(ctx, args)
+            }
+
+            // --------------------------
+            //    4.28 SetPriceFeedStatus
+            // --------------------------
+            pub fn set_price_feed_status(ctx: Context<UpdatePriceFeed>, symbol: String, status: FeedStatus) -> Result<()> {
+                set_price_feed_status_impl(ctx, symbol, status)
+            }
+
+            // --------------------------
+            //    4.29 AttestPrice
+            // --------------------------
+            pub fn attest_price(ctx: Context<AttestPrice>, feed_id: [u8; 32], price: u64, conf: u64) -> Result<()> {
+                attest_price_impl(ctx, feed_id, price, conf)
+            }
+
+            // --------------------------
+            //    4.31 VoidRound
+            // --------------------------
+            pub fn void_round(ctx: Context<VoidRound>, market_id: String, round_index: u32) -> Result<()> {
+                void_round_impl(ctx, market_id, round_index)
+            }
+
+            // --------------------------
+            //    4.32 ResolveDispute
+            // --------------------------
+            pub fn resolve_dispute(ctx: Context<ResolveDispute>, market_id: String, round_index: u32, uphold: bool) -> Result<()> {
+                resolve_dispute_impl(ctx, market_id, round_index, uphold)
+            }
+
+            // --------------------------
+            //    4.33 WithdrawRevenue
+            // --------------------------
+            pub fn withdraw_revenue(ctx: Context<WithdrawRevenue>, vault: RevenueVault, amount: u64) -> Result<()> {
+                withdraw_revenue_impl(ctx, vault, amount)
+            }
+
+            // --------------------------
+            //    4.34 MigrateRound
+            // --------------------------
+            pub fn migrate_round(ctx: Context<MigrateRound>, market_id: String, round_index: u32) -> Result<()> {
+                migrate_round_impl(ctx, market_id, round_index)
+            }
+
+            $($test_only)*
+        }
+    };
+}
+
+#[cfg(not(feature = "test-oracle"))]
+raven_program!();
 
+#[cfg(feature = "test-oracle")]
+raven_program! {
     // --------------------------
-    //    4.9 close_round
+    //    4.30 SetMockPrice
     // --------------------------
-    pub fn close_round(
-        ctx: Context<CloseRound>,
+    pub fn set_mock_price(
+        ctx: Context<SetMockPrice>,
+        feed_id: [u8; 32],
+        price: u64,
+        conf: u64,
+        publish_time: i64,
     ) -> Result<()> {
-        close_round_impl(ctx)
+        set_mock_price_impl(ctx, feed_id, price, conf, publish_time)
     }
 }
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use pyth_solana_receiver_sdk::price_update::get_feed_id_from_hex;
+
+use crate::error::MarketError;
+use crate::oracle::{require_fresh, OraclePrice, PriceSource, ReadPolicy};
+use crate::state::AttestedPrice;
+
+/// Prices posted by an attester through `attest_price`, for assets no oracle network covers.
+pub struct ManualAttested;
+
+impl PriceSource for ManualAttested {
+    // any 32 bytes as hex, usually a hash of the asset name
+    fn parse_feed_id(input: &str) -> Result<[u8; 32]> {
+        Ok(get_feed_id_from_hex(input).map_err(|_| MarketError::InvalidPriceFeed)?)
+    }
+
+    fn read(account: &AccountInfo, feed_id: &[u8; 32], clock: &Clock, policy: &ReadPolicy) -> Result<OraclePrice> {
+        require!(*account.owner == crate::ID, MarketError::InvalidOracleAccount);
+        let attested = AttestedPrice::try_deserialize(&mut &account.try_borrow_data()?[..])
+            .map_err(|_| MarketError::InvalidOracleAccount)?;
+        require!(attested.feed_id == *feed_id, MarketError::InvalidOracleAccount);
+        require_fresh(attested.publish_time, clock, policy.maximum_age)?;
+
+        Ok(OraclePrice {
+            price: attested.price,
+            conf: attested.conf,
+            publish_time: attested.publish_time,
+            verification: None,
+            ema: None,
+        })
+    }
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use pyth_solana_receiver_sdk::price_update::get_feed_id_from_hex;
+
+use crate::error::MarketError;
+use crate::oracle::{require_fresh, OraclePrice, PriceSource, ReadPolicy};
+use crate::state::MockPrice;
+
+/// Stands in for every provider under the `test-oracle` feature, so tests on a local
+/// validator can settle rounds on any price path they write with `set_mock_price`.
+pub struct MockOracle;
+
+impl PriceSource for MockOracle {
+    fn parse_feed_id(input: &str) -> Result<[u8; 32]> {
+        Ok(get_feed_id_from_hex(input).map_err(|_| MarketError::InvalidPriceFeed)?)
+    }
+
+    fn read(account: &AccountInfo, feed_id: &[u8; 32], clock: &Clock, policy: &ReadPolicy) -> Result<OraclePrice> {
+        require!(*account.owner == crate::ID, MarketError::InvalidOracleAccount);
+        let mock = MockPrice::try_deserialize(&mut &account.try_borrow_data()?[..])
+            .map_err(|_| MarketError::InvalidOracleAccount)?;
+        require!(mock.feed_id == *feed_id, MarketError::InvalidOracleAccount);
+        require_fresh(mock.publish_time, clock, policy.maximum_age)?;
+
+        Ok(OraclePrice {
+            price: mock.price,
+            conf: mock.conf,
+            publish_time: mock.publish_time,
+            verification: None,
+            ema: None,
+        })
+    }
+}
@@ -0,0 +1,161 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{BPS_DENOMINATOR, PRICE_DECIMALS};
+use crate::error::MarketError;
+use crate::state::{OracleProvider, OracleSource, PythVerification};
+
+pub mod manual;
+#[cfg(feature = "test-oracle")]
+pub mod mock;
+pub mod pyth;
+pub mod switchboard;
+
+pub use manual::ManualAttested;
+#[cfg(feature = "test-oracle")]
+pub use mock::MockOracle;
+pub use pyth::PythPull;
+pub use switchboard::SwitchboardOnDemand;
+
+/// A price read from any provider, scaled to `PRICE_DECIMALS`.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct OraclePrice {
+    pub price: u64,
+    pub conf: u64,
+    pub publish_time: i64,
+    // None for providers other than Pyth
+    pub verification: Option<PythVerification>,
+    // the provider's own moving average, where it publishes one
+    pub ema: Option<u64>,
+}
+
+/// What a read must satisfy, taken from the market and its feed rather than the caller.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct ReadPolicy {
+    pub maximum_age: u64,
+    pub min_verification: PythVerification,
+    pub max_conf_bps: u16,
+    // earliest and latest acceptable publish_time, when the read closes a round
+    pub published_after: Option<i64>,
+    pub published_by: Option<i64>,
+}
+
+/// One oracle provider. `account` is the price account passed to the instruction; each source
+/// checks its owner and that it carries `feed_id` before trusting it.
+pub trait PriceSource {
+    fn parse_feed_id(input: &str) -> Result<[u8; 32]>;
+
+    fn read(account: &AccountInfo, feed_id: &[u8; 32], clock: &Clock, policy: &ReadPolicy) -> Result<OraclePrice>;
+}
+
+impl OracleProvider {
+    pub fn parse_feed_id(self, input: &str) -> Result<[u8; 32]> {
+        match self {
+            OracleProvider::PythPull => PythPull::parse_feed_id(input),
+            OracleProvider::SwitchboardOnDemand => SwitchboardOnDemand::parse_feed_id(input),
+            OracleProvider::Manual => ManualAttested::parse_feed_id(input),
+        }
+    }
+
+    pub fn read(self, account: &AccountInfo, feed_id: &[u8; 32], clock: &Clock, policy: &ReadPolicy) -> Result<OraclePrice> {
+        #[cfg(feature = "test-oracle")]
+        let price = MockOracle::read(account, feed_id, clock, policy)?;
+        #[cfg(not(feature = "test-oracle"))]
+        let price = match self {
+            OracleProvider::PythPull => PythPull::read(account, feed_id, clock, policy),
+            OracleProvider::SwitchboardOnDemand => SwitchboardOnDemand::read(account, feed_id, clock, policy),
+            OracleProvider::Manual => ManualAttested::read(account, feed_id, clock, policy),
+        }?;
+        require!(price.price > 0, MarketError::InvalidOraclePrice);
+        require!(
+            price.conf as u128 * BPS_DENOMINATOR as u128 <= price.price as u128 * policy.max_conf_bps as u128,
+            MarketError::ConfidenceTooHigh
+        );
+        require!(
+            policy.published_after.map_or(true, |earliest| price.publish_time >= earliest),
+            MarketError::PricePublishedEarly
+        );
+        require!(
+            policy.published_by.map_or(true, |latest| price.publish_time <= latest),
+            MarketError::PricePublishedLate
+        );
+        Ok(price)
+    }
+}
+
+/// Median of several sources and how far apart they are.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct MedianPrice {
+    pub price: u64,
+    // (highest - lowest) / median, in basis points
+    pub spread_bps: u64,
+    // weakest Pyth verification among the sources read
+    pub verification: Option<PythVerification>,
+    // EMA of the primary source, the reference for CircuitBreaker
+    pub reference: Option<u64>,
+}
+
+/// Reads every source, each from the account at the same position in `accounts`.
+pub fn read_median(
+    sources: &[OracleSource],
+    accounts: &[&AccountInfo],
+    clock: &Clock,
+    policy: &ReadPolicy,
+) -> Result<MedianPrice> {
+    require!(!sources.is_empty() && accounts.len() >= sources.len(), MarketError::InvalidOracleAccount);
+    let mut prices = Vec::with_capacity(sources.len());
+    let mut verification = None;
+    let mut reference = None;
+    for (i, (source, account)) in sources.iter().zip(accounts).enumerate() {
+        let read = source.provider.read(account, &source.feed_id, clock, policy)?;
+        prices.push(read.price);
+        verification = PythVerification::weakest(verification, read.verification);
+        if i == 0 {
+            reference = read.ema;
+        }
+    }
+    Ok(MedianPrice {
+        verification,
+        reference,
+        ..median(&mut prices)
+    })
+}
+
+pub fn median(prices: &mut [u64]) -> MedianPrice {
+    prices.sort_unstable();
+    let mid = prices.len() / 2;
+    let price = if prices.len() % 2 == 0 {
+        ((prices[mid - 1] as u128 + prices[mid] as u128) / 2) as u64
+    } else {
+        prices[mid]
+    };
+    let spread = (prices[prices.len() - 1] - prices[0]) as u128;
+    MedianPrice {
+        price,
+        spread_bps: (spread * BPS_DENOMINATOR as u128).checked_div(price as u128).unwrap_or(0) as u64,
+        verification: None,
+        reference: None,
+    }
+}
+
+/// Rescales `value * 10^expo` to `PRICE_DECIMALS`. Rejects negative values and overflow.
+pub fn scale_price(value: i128, expo: i32) -> Result<u64> {
+    require!(value >= 0, MarketError::InvalidOraclePrice);
+    let shift = expo + PRICE_DECIMALS as i32;
+    let scaled = if shift >= 0 {
+        10i128.checked_pow(shift as u32).and_then(|m| value.checked_mul(m))
+    } else {
+        10i128.checked_pow(shift.unsigned_abs()).map(|d| value / d)
+    };
+    scaled
+        .and_then(|v| u64::try_from(v).ok())
+        .ok_or_else(|| error!(MarketError::InvalidOraclePrice))
+}
+
+/// Age check shared by every source.
+pub fn require_fresh(publish_time: i64, clock: &Clock, maximum_age: u64) -> Result<()> {
+    require!(
+        publish_time.saturating_add(maximum_age as i64) >= clock.unix_timestamp,
+        MarketError::PriceStale
+    );
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use pyth_solana_receiver_sdk::price_update::{get_feed_id_from_hex, PriceUpdateV2, VerificationLevel};
+
+use crate::error::MarketError;
+use crate::oracle::{scale_price, OraclePrice, PriceSource, ReadPolicy};
+use crate::state::PythVerification;
+
+/// Pyth pull oracle: a `PriceUpdateV2` account posted through the Pyth receiver program.
+pub struct PythPull;
+
+impl From<PythVerification> for VerificationLevel {
+    fn from(level: PythVerification) -> Self {
+        match level {
+            PythVerification::Partial { num_signatures } => VerificationLevel::Partial { num_signatures },
+            PythVerification::Full => VerificationLevel::Full,
+        }
+    }
+}
+
+impl From<VerificationLevel> for PythVerification {
+    fn from(level: VerificationLevel) -> Self {
+        match level {
+            VerificationLevel::Partial { num_signatures } => PythVerification::Partial { num_signatures },
+            VerificationLevel::Full => PythVerification::Full,
+        }
+    }
+}
+
+impl PriceSource for PythPull {
+    // hex, with or without 0x
+    fn parse_feed_id(input: &str) -> Result<[u8; 32]> {
+        Ok(get_feed_id_from_hex(input).map_err(|_| MarketError::InvalidPriceFeed)?)
+    }
+
+    fn read(account: &AccountInfo, feed_id: &[u8; 32], clock: &Clock, policy: &ReadPolicy) -> Result<OraclePrice> {
+        require!(*account.owner == pyth_solana_receiver_sdk::ID, MarketError::InvalidOracleAccount);
+        let price_update = PriceUpdateV2::try_deserialize(&mut &account.try_borrow_data()?[..])
+            .map_err(|_| MarketError::InvalidOracleAccount)?;
+        require!(
+            price_update.verification_level.gte(policy.min_verification.into()),
+            MarketError::InsufficientVerification
+        );
+        let pyth_price = price_update.get_price_no_older_than_with_custom_verification_level(
+            clock, policy.maximum_age, feed_id, policy.min_verification.into(),
+        )?;
+        require!(pyth_price.price > 0, MarketError::InvalidPythPrice);
+        let message = &price_update.price_message;
+
+        Ok(OraclePrice {
+            price: scale_price(pyth_price.price as i128, pyth_price.exponent)?,
+            conf: scale_price(pyth_price.conf as i128, pyth_price.exponent)?,
+            publish_time: pyth_price.publish_time,
+            verification: Some(price_update.verification_level.into()),
+            ema: scale_price(message.ema_price as i128, message.exponent).ok(),
+        })
+    }
+}
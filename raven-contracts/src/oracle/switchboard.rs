@@ -0,0 +1,52 @@
+use std::str::FromStr;
+
+use anchor_lang::prelude::*;
+use switchboard_on_demand::{PullFeedAccountData, SWITCHBOARD_ON_DEMAND_PROGRAM_ID};
+
+use crate::constants::PRICE_DECIMALS;
+use crate::error::MarketError;
+use crate::oracle::{require_fresh, OraclePrice, PriceSource, ReadPolicy};
+
+/// Switchboard on-demand: the feed id is the address of the pull feed account itself.
+pub struct SwitchboardOnDemand;
+
+impl PriceSource for SwitchboardOnDemand {
+    // base58 address of the pull feed
+    fn parse_feed_id(input: &str) -> Result<[u8; 32]> {
+        Ok(Pubkey::from_str(input).map_err(|_| MarketError::InvalidPriceFeed)?.to_bytes())
+    }
+
+    fn read(account: &AccountInfo, feed_id: &[u8; 32], clock: &Clock, policy: &ReadPolicy) -> Result<OraclePrice> {
+        require!(account.key.to_bytes() == *feed_id, MarketError::InvalidOracleAccount);
+        require!(
+            account.owner.to_bytes() == SWITCHBOARD_ON_DEMAND_PROGRAM_ID.to_bytes(),
+            MarketError::InvalidOracleAccount
+        );
+
+        let data = account.try_borrow_data()?;
+        let feed = PullFeedAccountData::parse(data).map_err(|_| MarketError::InvalidOracleAccount)?;
+        // the switchboard crate builds against its own solana-program
+        let sb_clock = solana_program::clock::Clock {
+            slot: clock.slot,
+            epoch_start_timestamp: clock.epoch_start_timestamp,
+            epoch: clock.epoch,
+            leader_schedule_epoch: clock.leader_schedule_epoch,
+            unix_timestamp: clock.unix_timestamp,
+        };
+        let mut value = feed.value(&sb_clock).map_err(|_| MarketError::PriceStale)?;
+        let mut std_dev = feed.std_dev().unwrap_or_default();
+        let publish_time = feed.result_ts();
+        require_fresh(publish_time, clock, policy.maximum_age)?;
+        require!(value.is_sign_positive(), MarketError::InvalidOraclePrice);
+
+        value.rescale(PRICE_DECIMALS);
+        std_dev.rescale(PRICE_DECIMALS);
+        Ok(OraclePrice {
+            price: u64::try_from(value.mantissa()).map_err(|_| MarketError::InvalidOraclePrice)?,
+            conf: u64::try_from(std_dev.mantissa().unsigned_abs()).map_err(|_| MarketError::InvalidOraclePrice)?,
+            publish_time,
+            verification: None,
+            ema: None,
+        })
+    }
+}
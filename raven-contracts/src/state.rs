@@ -1,46 +1,475 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::{
+    BPS_DENOMINATOR, BUYBACK_SEED, HISTORY_LEN, MAX_ROLE_GRANTS, MAX_SECONDARY_SOURCES, STAKING_SEED,
+};
+use crate::error::MarketError;
+
+/// `amount * bps / 10_000`, computed in u128 so large pools cannot overflow.
+pub fn bps_of(amount: u64, bps: u16) -> u64 {
+    (amount as u128 * bps as u128 / BPS_DENOMINATOR as u128) as u64
+}
+
 #[account]
 #[derive(PartialEq, Debug)]
 pub struct State {
     pub admin_pubkey: Pubkey,
+    // set by propose_admin, becomes admin_pubkey once it signs accept_admin
+    pub pending_admin: Option<Pubkey>,
     pub escrow_pubkey: Pubkey,
     pub escrow_bump: u8,
-    pub creator_fee_percent: u8,
+    pub creator_fee_bps: u16,
+
+    pub revenue_split: RevenueSplit,
+    pub rm_mint: Pubkey,
+    pub total_rm_burned: u64,
+    // tripped by check_solvency or the guardian, blocks betting and market creation
+    pub paused: bool,
+    // may pause the protocol but not resume it, Pubkey::default() when unset
+    pub guardian: Pubkey,
 
     pub allowed_pricefeeds: Vec<PriceFeedConfig>,
     pub markets: Vec<Market>,
 }
 
+impl State {
+    /// Records `new_admin` as the pending admin; `None` withdraws a pending proposal.
+    pub fn propose_admin(&mut self, new_admin: Option<Pubkey>) -> Result<()> {
+        require!(new_admin != Some(self.admin_pubkey), MarketError::InvalidArgument);
+        self.pending_admin = new_admin;
+        Ok(())
+    }
+
+    /// Hands the admin role to `signer` if it is the pending admin. Returns the previous admin.
+    pub fn accept_admin(&mut self, signer: &Pubkey) -> Result<Pubkey> {
+        require!(self.pending_admin == Some(*signer), MarketError::IllegalOwner);
+        let previous = self.admin_pubkey;
+        self.admin_pubkey = *signer;
+        self.pending_admin = None;
+        Ok(previous)
+    }
+
+    /// Shared authorization guard: the admin holds every role, anyone else needs a grant.
+    pub fn require_role(&self, roles: Option<&Roles>, key: &Pubkey, role: Role) -> Result<()> {
+        if *key == self.admin_pubkey {
+            return Ok(());
+        }
+        require!(roles.is_some_and(|r| r.has(key, role)), MarketError::MissingRole);
+        Ok(())
+    }
+
+    /// The feed a market settles against, `None` if the market or its feed is gone.
+    pub fn market_feed(&self, market_id: &str) -> Option<&PriceFeedConfig> {
+        let mk = self.markets.iter().find(|m| m.market_id == market_id)?;
+        self.allowed_pricefeeds.iter().find(|pf| pf.feed_id == mk.feed_id)
+    }
+
+    pub fn market_feed_status(&self, market_id: &str) -> Option<FeedStatus> {
+        self.market_feed(market_id).map(|pf| pf.status)
+    }
+
+    pub fn set_creator_fee_bps(&mut self, creator_fee_bps: u16) -> Result<()> {
+        require!(creator_fee_bps as u64 <= BPS_DENOMINATOR, MarketError::InvalidArgument);
+        self.creator_fee_bps = creator_fee_bps;
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, PartialEq, Debug, Clone, Copy)]
+pub enum Role {
+    // add, update and remove price feeds
+    FeedManager,
+    // resume markets and slash creation bonds
+    MarketOperator,
+    // pause markets
+    Pauser,
+    // open and settle rounds
+    Keeper,
+    // revenue config and vault migration
+    Treasurer,
+    // post prices for Manual feeds
+    Attester,
+}
+
+impl Role {
+    pub fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// Keys granted roles by the admin. Each grant holds a bitmask of `Role::bit`.
+#[account]
+#[derive(InitSpace, PartialEq, Debug)]
+pub struct Roles {
+    #[max_len(MAX_ROLE_GRANTS)]
+    pub grants: Vec<RoleGrant>,
+}
+
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, PartialEq, Debug, Clone, Copy)]
+pub struct RoleGrant {
+    pub key: Pubkey,
+    pub roles: u8,
+}
+
+impl Roles {
+    pub fn has(&self, key: &Pubkey, role: Role) -> bool {
+        self.grants.iter().any(|g| g.key == *key && g.roles & role.bit() != 0)
+    }
+
+    pub fn grant(&mut self, key: Pubkey, role: Role) -> Result<()> {
+        if let Some(g) = self.grants.iter_mut().find(|g| g.key == key) {
+            g.roles |= role.bit();
+            return Ok(());
+        }
+        require!(self.grants.len() < MAX_ROLE_GRANTS, MarketError::InvalidArgument);
+        self.grants.push(RoleGrant { key, roles: role.bit() });
+        Ok(())
+    }
+
+    /// Drops the grant entirely once its last role is revoked.
+    pub fn revoke(&mut self, key: &Pubkey, role: Role) -> Result<()> {
+        let pos_opt = self.grants.iter().position(|g| g.key == *key && g.roles & role.bit() != 0);
+        require!(pos_opt.is_some(), MarketError::MissingRole);
+        let idx = pos_opt.unwrap();
+        self.grants[idx].roles &= !role.bit();
+        if self.grants[idx].roles == 0 {
+            self.grants.remove(idx);
+        }
+        Ok(())
+    }
+}
+
+/// How the protocol's share of fees is divided, in basis points summing to 10_000.
+#[derive(AnchorSerialize, AnchorDeserialize, PartialEq, Debug, Clone, Copy)]
+pub struct RevenueSplit {
+    pub operations_bps: u16,
+    pub buyback_bps: u16,
+    pub staking_bps: u16,
+}
+
+impl Default for RevenueSplit {
+    fn default() -> Self {
+        Self {
+            operations_bps: BPS_DENOMINATOR as u16,
+            buyback_bps: 0,
+            staking_bps: 0,
+        }
+    }
+}
+
+impl RevenueSplit {
+    pub fn is_valid(&self) -> bool {
+        self.operations_bps as u64 + self.buyback_bps as u64 + self.staking_bps as u64 == BPS_DENOMINATOR
+    }
+
+    /// Returns (operations, buyback, staking). Rounding dust goes to operations.
+    pub fn split(&self, amount: u64) -> (u64, u64, u64) {
+        let buyback = bps_of(amount, self.buyback_bps);
+        let staking = bps_of(amount, self.staking_bps);
+        let operations = amount.saturating_sub(buyback).saturating_sub(staking);
+        (operations, buyback, staking)
+    }
+}
+
+/// The protocol vaults RevenueSplit pays into.
+#[derive(AnchorSerialize, AnchorDeserialize, PartialEq, Debug, Clone, Copy)]
+pub enum RevenueVault {
+    Buyback,
+    Staking,
+}
+
+impl RevenueVault {
+    pub fn seed(self) -> &'static [u8] {
+        match self {
+            RevenueVault::Buyback => BUYBACK_SEED,
+            RevenueVault::Staking => STAKING_SEED,
+        }
+    }
+}
+
 #[account]
 #[derive(PartialEq, Debug)]
 pub struct PriceFeedConfig {
     pub symbol: String,
-    pub pyth_feed_id: String,
+    pub provider: OracleProvider,
+    // Pyth price feed id, Switchboard pull feed address, or the key of an attested price
+    pub feed_id: [u8; 32],
     pub create_market_lamports: u64,
     pub min_betting_lamports: u64,
     pub min_betting_period: u16,
     pub max_betting_period: u16,
     pub min_settling_period: u16,
     pub max_settling_period: u16,
+    pub min_creator_fee_bps: u16,
+    pub max_creator_fee_bps: u16,
+    // create_market_lamports is held in escrow as a refundable bond instead of paid as a fee
+    pub creation_bond: bool,
+    pub status: FeedStatus,
+    // read alongside the primary source; settlement uses the median of all of them
+    pub secondary_sources: Vec<OracleSource>,
+    // rounds are voided when the sources spread further apart than this, relative to the median
+    pub max_divergence_bps: u16,
+    // Pyth updates verified less than this are rejected; ignored by other providers
+    pub min_verification: PythVerification,
+    // seconds after end_time a round may wait for a price before anyone can void it; 0 never
+    pub outage_grace_period: u32,
+    pub breaker: CircuitBreaker,
+    // the loosest oracle policy a market on this feed may choose
+    pub oracle_limits: OraclePolicy,
+}
+
+impl PriceFeedConfig {
+    pub fn sources_valid(&self) -> bool {
+        self.secondary_sources.len() <= MAX_SECONDARY_SOURCES
+            && self.secondary_sources.iter().all(|s| s.feed_id != self.feed_id)
+            && (self.secondary_sources.is_empty()
+                || (self.max_divergence_bps > 0 && self.max_divergence_bps as u64 <= BPS_DENOMINATOR))
+    }
+
+    /// Only Pyth publishes the EMA the breaker's reference check compares against.
+    pub fn breaker_valid(&self) -> bool {
+        self.breaker.is_valid()
+            && (self.breaker.max_reference_deviation_bps == 0 || self.provider == OracleProvider::PythPull)
+    }
+}
+
+/// Oracle tolerances a market settles under, chosen at create_market within its feed's limits.
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, PartialEq, Debug, Clone, Copy)]
+pub struct OraclePolicy {
+    // seconds a price may be old when the crank reads it
+    pub max_price_age: u32,
+    // widest confidence interval, relative to the price
+    pub max_conf_bps: u16,
+    // seconds after end_time a spot closing price may have been published
+    pub max_publish_delay: u32,
+}
+
+impl OraclePolicy {
+    // feeds and markets migrated from before the policy existed
+    pub const LEGACY: OraclePolicy = OraclePolicy {
+        max_price_age: 60,
+        max_conf_bps: BPS_DENOMINATOR as u16,
+        max_publish_delay: 60,
+    };
+
+    pub fn is_valid(&self) -> bool {
+        self.max_price_age > 0 && self.max_conf_bps > 0 && self.max_conf_bps as u64 <= BPS_DENOMINATOR
+    }
+
+    pub fn within(&self, limits: &OraclePolicy) -> bool {
+        self.max_price_age <= limits.max_price_age
+            && self.max_conf_bps <= limits.max_conf_bps
+            && self.max_publish_delay <= limits.max_publish_delay
+    }
+
+    pub fn clamp_to(&mut self, limits: &OraclePolicy) {
+        self.max_price_age = self.max_price_age.min(limits.max_price_age);
+        self.max_conf_bps = self.max_conf_bps.min(limits.max_conf_bps);
+        self.max_publish_delay = self.max_publish_delay.min(limits.max_publish_delay);
+    }
+
+    /// No price published within `max_publish_delay` of `end_time` is fresh enough to read at
+    /// `now` any more, so a spot close can no longer succeed.
+    pub fn close_window_missed(&self, end_time: u32, now: u32) -> bool {
+        now as u64 > end_time as u64 + self.max_publish_delay as u64 + self.max_price_age as u64
+    }
+}
+
+/// Holds back settlement on closing prices that moved too far. A tripped round waits for the
+/// guardian and is voided if nobody reviews it within `dispute_timeout` seconds.
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Default, PartialEq, Debug, Clone, Copy)]
+pub struct CircuitBreaker {
+    // largest move from the lock price to the close price; 0 disables
+    pub max_move_bps: u16,
+    // largest distance of the close price from the primary source's EMA; 0 disables
+    pub max_reference_deviation_bps: u16,
+    pub dispute_timeout: u32,
+}
+
+impl CircuitBreaker {
+    pub fn is_valid(&self) -> bool {
+        let enabled = self.max_move_bps > 0 || self.max_reference_deviation_bps > 0;
+        self.max_move_bps as u64 <= BPS_DENOMINATOR
+            && self.max_reference_deviation_bps as u64 <= BPS_DENOMINATOR
+            && (!enabled || self.dispute_timeout > 0)
+    }
+
+    pub fn trips(&self, start_price: u64, end_price: u64, reference: Option<u64>) -> bool {
+        let exceeds = |limit: u16, from: u64| {
+            limit > 0
+                && from > 0
+                && (from.abs_diff(end_price) as u128 * BPS_DENOMINATOR as u128 / from as u128) > limit as u128
+        };
+        exceeds(self.max_move_bps, start_price)
+            || reference.is_some_and(|r| exceeds(self.max_reference_deviation_bps, r))
+    }
+}
+
+/// How many Wormhole guardian signatures were checked on a Pyth update.
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, PartialEq, Debug, Clone, Copy)]
+pub enum PythVerification {
+    Partial { num_signatures: u8 },
+    Full,
+}
+
+impl PythVerification {
+    pub fn weakest(a: Option<Self>, b: Option<Self>) -> Option<Self> {
+        match (a, b) {
+            (Some(PythVerification::Partial { num_signatures: x }), Some(PythVerification::Partial { num_signatures: y })) => {
+                Some(PythVerification::Partial { num_signatures: x.min(y) })
+            }
+            (Some(p @ PythVerification::Partial { .. }), _) | (_, Some(p @ PythVerification::Partial { .. })) => Some(p),
+            (Some(v), _) | (_, Some(v)) => Some(v),
+            (None, None) => None,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, PartialEq, Debug, Clone, Copy)]
+pub struct OracleSource {
+    pub provider: OracleProvider,
+    pub feed_id: [u8; 32],
+}
+
+/// Where a feed's prices come from. See `oracle` for how each one is read.
+#[derive(AnchorSerialize, AnchorDeserialize, PartialEq, Debug, Clone, Copy)]
+pub enum OracleProvider {
+    PythPull,
+    SwitchboardOnDemand,
+    // posted by a key holding Role::Attester through attest_price
+    Manual,
+}
+
+/// Lifecycle of a listed feed. Only `Retired` feeds can be removed.
+#[derive(AnchorSerialize, AnchorDeserialize, PartialEq, Debug, Clone, Copy)]
+pub enum FeedStatus {
+    Listed,
+    // no new markets or rounds, running rounds finish and betting into them continues
+    Deprecated,
+    // no betting; running rounds can still settle and be claimed
+    Retired,
+}
+
+impl FeedStatus {
+    pub fn can_become(self, next: FeedStatus) -> bool {
+        matches!(
+            (self, next),
+            (FeedStatus::Listed, FeedStatus::Deprecated)
+                | (FeedStatus::Deprecated, FeedStatus::Listed)
+                | (FeedStatus::Deprecated, FeedStatus::Retired)
+        )
+    }
 }
 
 #[account]
 #[derive(PartialEq, Debug)]
 pub struct Market {
     pub market_id: String,
-    pub pyth_feed_id: String,
+    pub provider: OracleProvider,
+    pub feed_id: [u8; 32],
     pub creation_time: u32,
     pub paused: bool,
-    pub fee_rate: u8,       
+    pub fee_rate_bps: u16,
     pub min_betting_price: u64,
     pub betting_period: u16, 
     pub settling_period: u16,
     pub creator_pubkey: Pubkey,
+    pub creator_fee_recipient: Pubkey,
+    pub creator_fees_accrued: u64,
+    pub creator_fee_bps: u16,
+    pub creator_weight: u16,
+    pub co_creators: Vec<CoCreator>,
+    pub bond_lamports: u64,
+
+    // escrow liabilities: stakes in unsettled rounds, settled but unpaid bet results
+    pub open_stakes: u64,
+    pub unpaid_winnings: u64,
+    // rounds locked and not yet settled, with or without bets
+    pub open_rounds: u32,
+    // funds still sit in the global escrow, see migrate_market_vault
+    pub legacy_escrow: bool,
+    pub settlement: SettlementMode,
+    // a round was voided for want of a price; no betting until process_round reads a fresh one
+    pub awaiting_price: bool,
+    pub oracle_policy: OraclePolicy,
 
     pub round_index: u32,
 }
 
+/// How a round's closing price is taken.
+#[derive(AnchorSerialize, AnchorDeserialize, PartialEq, Debug, Clone, Copy)]
+pub enum SettlementMode {
+    // the price read by the first crank at or after end_time
+    Spot,
+    // the average of samples the crank records during the settling window, at most one
+    // every `sample_interval` seconds; rounds with fewer than `min_samples` are voided
+    Twap { sample_interval: u16, min_samples: u16 },
+}
+
+impl SettlementMode {
+    pub fn is_valid(&self, settling_period: u16) -> bool {
+        match *self {
+            SettlementMode::Spot => true,
+            SettlementMode::Twap { sample_interval, min_samples } => {
+                sample_interval > 0
+                    && min_samples > 0
+                    && (min_samples as u32 - 1) * sample_interval as u32 <= settling_period as u32
+            }
+        }
+    }
+}
+
+impl Market {
+    pub fn accrued_creator_fees(&self) -> u64 {
+        self.co_creators.iter()
+            .fold(self.creator_fees_accrued, |acc, c| acc.saturating_add(c.fees_accrued))
+    }
+
+    /// Everything the market's escrow vault owes.
+    pub fn liabilities(&self) -> u64 {
+        self.open_stakes
+            .saturating_add(self.unpaid_winnings)
+            .saturating_add(self.accrued_creator_fees())
+            .saturating_add(self.bond_lamports)
+    }
+
+    /// Splits a creator fee between the creator and co-creators by weight.
+    /// Rounding dust stays with the creator.
+    pub fn accrue_creator_fee(&mut self, amount: u64) {
+        let total_weight = self.co_creators.iter()
+            .fold(self.creator_weight as u64, |acc, c| acc + c.weight as u64);
+        let mut remaining = amount;
+        for co in self.co_creators.iter_mut() {
+            let share = (amount as u128 * co.weight as u128)
+                .checked_div(total_weight as u128)
+                .unwrap_or(0) as u64;
+            co.fees_accrued = co.fees_accrued.saturating_add(share);
+            remaining = remaining.saturating_sub(share);
+        }
+        self.creator_fees_accrued = self.creator_fees_accrued.saturating_add(remaining);
+    }
+
+    /// Pulls the market's periods and oracle policy into the feed's bounds and adopts its
+    /// minimum bet. Rounds already locked keep their betting and closing times. Fails when the
+    /// new settling period no longer fits the market's TWAP schedule.
+    pub fn apply_feed_limits(&mut self, pf: &PriceFeedConfig) -> Result<()> {
+        self.betting_period = self.betting_period.clamp(pf.min_betting_period, pf.max_betting_period);
+        self.settling_period = self.settling_period.clamp(pf.min_settling_period, pf.max_settling_period);
+        require!(self.settlement.is_valid(self.settling_period), MarketError::InvalidArgument);
+        self.min_betting_price = pf.min_betting_lamports;
+        self.oracle_policy.clamp_to(&pf.oracle_limits);
+        Ok(())
+    }
+}
+
+/// A partner wallet sharing a market's creator fees by weight.
+#[derive(AnchorSerialize, AnchorDeserialize, PartialEq, Debug, Clone)]
+pub struct CoCreator {
+    pub recipient: Pubkey,
+    pub weight: u16,
+    pub fees_accrued: u64,
+}
+
 #[account]
 #[derive(InitSpace, Default, PartialEq, Debug)]
 pub struct Round {
@@ -58,6 +487,176 @@ pub struct Round {
     pub bets: Vec<Bet>,
 
     pub settled: bool,
+    // oracle sources disagreed; every stake is returned and no fee is taken
+    pub voided: bool,
+
+    // SettlementMode::Twap samples taken so far
+    pub twap_sum: u128,
+    pub twap_samples: u16,
+    pub last_sample_time: u32,
+
+    // weakest Pyth verification among the updates the round was locked and settled on
+    pub verification: Option<PythVerification>,
+    // when the close price tripped the feed's circuit breaker; 0 if not under dispute
+    pub disputed_at: u32,
+    // last second bets are taken, fixed when the round locks
+    pub betting_end: u32,
+}
+
+impl Round {
+    pub fn record_sample(&mut self, price: u64, now: u32) {
+        self.twap_sum += price as u128;
+        self.twap_samples += 1;
+        self.last_sample_time = now;
+    }
+
+    pub fn twap(&self) -> Option<u64> {
+        self.twap_sum.checked_div(self.twap_samples as u128).map(|p| p as u64)
+    }
+
+    /// Locked, unsettled, still without a closing price, and more than `grace_period` seconds
+    /// past end_time.
+    pub fn is_stalled(&self, now: u32, grace_period: u32) -> bool {
+        grace_period > 0
+            && self.start_time > 0
+            && !self.settled
+            && self.disputed_at == 0
+            && self.end_price == 0
+            && now > self.end_time.saturating_add(grace_period)
+    }
+
+    /// Under dispute for longer than `timeout` seconds without a guardian decision.
+    pub fn dispute_expired(&self, now: u32, timeout: u32) -> bool {
+        self.disputed_at > 0 && !self.settled && now > self.disputed_at.saturating_add(timeout)
+    }
+
+    /// Voids the round and returns every stake; returns the total now payable.
+    pub fn void(&mut self) -> u64 {
+        self.voided = true;
+        self.settled = true;
+        for bet in self.bets.iter_mut() {
+            bet.result = bet.amount;
+        }
+        self.total_up.saturating_add(self.total_down)
+    }
+
+    /// Settled results nobody has been paid yet.
+    pub fn unpaid(&self) -> u64 {
+        self.bets.iter()
+            .filter(|b| !b.refunded)
+            .fold(0u64, |acc, b| acc.saturating_add(b.result))
+    }
+
+    /// Every bet has been paid out and recorded in its owner's stats.
+    pub fn is_closed_out(&self) -> bool {
+        self.bets.iter().all(|b| b.refunded)
+    }
+
+    /// Settled results `user` has not been paid yet.
+    pub fn payable_to(&self, user: &Pubkey) -> u64 {
+        self.bets.iter()
+            .filter(|b| b.user == *user && !b.refunded)
+            .fold(0u64, |acc, b| acc.saturating_add(b.result))
+    }
+
+    /// Marks every open bet of `user` as paid out, losing bets included,
+    /// and returns (staked, returned) for those bets.
+    pub fn close_out(&mut self, user: &Pubkey) -> (u64, u64) {
+        let mut staked = 0u64;
+        let mut returned = 0u64;
+        for bet in self.bets.iter_mut().filter(|b| b.user == *user && !b.refunded) {
+            staked = staked.saturating_add(bet.amount);
+            returned = returned.saturating_add(bet.result);
+            bet.refunded = true;
+        }
+        (staked, returned)
+    }
+}
+
+#[account]
+#[derive(InitSpace, Default, PartialEq, Debug)]
+pub struct UserStats {
+    pub user: Pubkey,
+    pub rounds_played: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub total_wagered: u64,
+    pub total_won: u64,
+    pub net_pnl: i64,
+    // positive for consecutive wins, negative for consecutive losses
+    pub current_streak: i32,
+}
+
+impl UserStats {
+    /// Records the outcome of one settled round. Returning exactly the stake counts as neither.
+    pub fn record_round(&mut self, staked: u64, returned: u64) {
+        self.total_won = self.total_won.saturating_add(returned);
+        self.net_pnl = self.net_pnl.saturating_add(returned as i64 - staked as i64);
+        if returned > staked {
+            self.wins += 1;
+            self.current_streak = if self.current_streak > 0 { self.current_streak + 1 } else { 1 };
+        } else if returned < staked {
+            self.losses += 1;
+            self.current_streak = if self.current_streak < 0 { self.current_streak - 1 } else { -1 };
+        }
+    }
+}
+
+/// Latest price posted for a `Manual` feed.
+#[account]
+#[derive(InitSpace, PartialEq, Debug)]
+pub struct AttestedPrice {
+    pub feed_id: [u8; 32],
+    // scaled to PRICE_DECIMALS
+    pub price: u64,
+    pub conf: u64,
+    pub publish_time: i64,
+    pub attester: Pubkey,
+}
+
+/// Price served to every feed when built with the `test-oracle` feature.
+#[cfg(feature = "test-oracle")]
+#[account]
+#[derive(InitSpace, PartialEq, Debug)]
+pub struct MockPrice {
+    pub feed_id: [u8; 32],
+    // scaled to PRICE_DECIMALS
+    pub price: u64,
+    pub conf: u64,
+    pub publish_time: i64,
+}
+
+/// Ring buffer of a market's most recent settled rounds, kept after Round accounts are closed.
+#[account]
+#[derive(InitSpace, PartialEq, Debug)]
+pub struct MarketHistory {
+    #[max_len(32)]
+    pub market_id: String,
+    // next slot to be written
+    pub head: u16,
+    pub count: u16,
+    pub outcomes: [RoundOutcome; HISTORY_LEN],
+}
+
+impl MarketHistory {
+    pub fn push(&mut self, outcome: RoundOutcome) {
+        self.outcomes[self.head as usize] = outcome;
+        self.head = ((self.head as usize + 1) % HISTORY_LEN) as u16;
+        self.count = (self.count as usize + 1).min(HISTORY_LEN) as u16;
+    }
+}
+
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, PartialEq, Debug, Clone, Copy, Default)]
+pub struct RoundOutcome {
+    pub round_index: u32,
+    pub start_price: u64,
+    pub end_price: u64,
+    pub winner: Option<Direction>,
+    pub total_up: u64,
+    pub total_down: u64,
+    pub total_fee: u64,
+    pub settled_at: u32,
+    pub voided: bool,
 }
 
 #[derive(InitSpace, AnchorSerialize, AnchorDeserialize, PartialEq, Debug, Clone, Copy)]
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use raven_0_1_0::error::MarketError;
+use raven_0_1_0::*;
+
+mod common;
+use common::state;
+
+#[test]
+fn handover_needs_the_proposed_key() {
+    let admin = Pubkey::new_unique();
+    let next = Pubkey::new_unique();
+    let mut state = state(admin);
+
+    state.propose_admin(Some(next)).unwrap();
+    assert_eq!(state.pending_admin, Some(next));
+    assert_eq!(state.admin_pubkey, admin);
+
+    let err = state.accept_admin(&Pubkey::new_unique()).unwrap_err();
+    assert_eq!(err, MarketError::IllegalOwner.into());
+
+    assert_eq!(state.accept_admin(&next).unwrap(), admin);
+    assert_eq!(state.admin_pubkey, next);
+    assert_eq!(state.pending_admin, None);
+}
+
+#[test]
+fn accept_without_proposal_fails() {
+    let admin = Pubkey::new_unique();
+    let mut state = state(admin);
+
+    let err = state.accept_admin(&admin).unwrap_err();
+    assert_eq!(err, MarketError::IllegalOwner.into());
+    assert_eq!(state.admin_pubkey, admin);
+}
+
+#[test]
+fn proposal_can_be_replaced_or_withdrawn() {
+    let admin = Pubkey::new_unique();
+    let first = Pubkey::new_unique();
+    let second = Pubkey::new_unique();
+    let mut state = state(admin);
+
+    state.propose_admin(Some(first)).unwrap();
+    state.propose_admin(Some(second)).unwrap();
+    assert_eq!(state.accept_admin(&first).unwrap_err(), MarketError::IllegalOwner.into());
+
+    state.propose_admin(None).unwrap();
+    assert_eq!(state.accept_admin(&second).unwrap_err(), MarketError::IllegalOwner.into());
+    assert_eq!(state.admin_pubkey, admin);
+}
+
+#[test]
+fn proposing_current_admin_is_rejected() {
+    let admin = Pubkey::new_unique();
+    let mut state = state(admin);
+
+    let err = state.propose_admin(Some(admin)).unwrap_err();
+    assert_eq!(err, MarketError::InvalidArgument.into());
+    assert_eq!(state.pending_admin, None);
+}
+
+#[test]
+fn creator_fee_bps_is_bounded() {
+    let mut state = state(Pubkey::new_unique());
+
+    state.set_creator_fee_bps(250).unwrap();
+    assert_eq!(state.creator_fee_bps, 250);
+    state.set_creator_fee_bps(BPS_DENOMINATOR as u16).unwrap();
+    assert_eq!(state.creator_fee_bps, BPS_DENOMINATOR as u16);
+
+    let err = state.set_creator_fee_bps(BPS_DENOMINATOR as u16 + 1).unwrap_err();
+    assert_eq!(err, MarketError::InvalidArgument.into());
+    assert_eq!(state.creator_fee_bps, BPS_DENOMINATOR as u16);
+}
@@ -0,0 +1,74 @@
+#![allow(dead_code)]
+
+use anchor_lang::prelude::*;
+use raven_0_1_0::*;
+
+pub fn state(admin: Pubkey) -> State {
+    State {
+        admin_pubkey: admin,
+        pending_admin: None,
+        escrow_pubkey: Pubkey::new_unique(),
+        escrow_bump: 255,
+        creator_fee_bps: 100,
+        revenue_split: RevenueSplit::default(),
+        rm_mint: Pubkey::default(),
+        total_rm_burned: 0,
+        paused: false,
+        guardian: Pubkey::default(),
+        allowed_pricefeeds: vec![],
+        markets: vec![],
+    }
+}
+
+pub fn market(market_id: &str) -> Market {
+    Market {
+        market_id: market_id.to_string(),
+        provider: OracleProvider::PythPull,
+        feed_id: [1; 32],
+        creation_time: 0,
+        paused: false,
+        fee_rate_bps: 300,
+        min_betting_price: 1_000,
+        betting_period: 60,
+        settling_period: 60,
+        creator_pubkey: Pubkey::new_unique(),
+        creator_fee_recipient: Pubkey::new_unique(),
+        creator_fees_accrued: 0,
+        creator_fee_bps: 100,
+        creator_weight: 1,
+        co_creators: vec![],
+        bond_lamports: 0,
+        open_stakes: 0,
+        unpaid_winnings: 0,
+        open_rounds: 0,
+        legacy_escrow: false,
+        settlement: SettlementMode::Spot,
+        awaiting_price: false,
+        oracle_policy: OraclePolicy::LEGACY,
+        round_index: 0,
+    }
+}
+
+pub fn price_feed(symbol: &str) -> PriceFeedConfig {
+    PriceFeedConfig {
+        symbol: symbol.to_string(),
+        provider: OracleProvider::PythPull,
+        feed_id: [1; 32],
+        create_market_lamports: 0,
+        min_betting_lamports: 1_000,
+        min_betting_period: 30,
+        max_betting_period: 300,
+        min_settling_period: 30,
+        max_settling_period: 300,
+        min_creator_fee_bps: 0,
+        max_creator_fee_bps: 1_000,
+        creation_bond: false,
+        status: FeedStatus::Listed,
+        secondary_sources: vec![],
+        max_divergence_bps: 0,
+        min_verification: PythVerification::Full,
+        outage_grace_period: 0,
+        breaker: CircuitBreaker::default(),
+        oracle_limits: OraclePolicy::LEGACY,
+    }
+}
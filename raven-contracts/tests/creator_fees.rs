@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use raven_0_1_0::*;
+
+mod common;
+use common::market;
+
+fn co_creator(weight: u16) -> CoCreator {
+    CoCreator {
+        recipient: Pubkey::new_unique(),
+        weight,
+        fees_accrued: 0,
+    }
+}
+
+#[test]
+fn creator_fee_is_shared_by_weight() {
+    let mut mk = market("BTC-1");
+    mk.creator_weight = 2;
+    mk.co_creators = vec![co_creator(1), co_creator(1)];
+
+    mk.accrue_creator_fee(400);
+    assert_eq!(mk.creator_fees_accrued, 200);
+    assert_eq!(mk.co_creators[0].fees_accrued, 100);
+    assert_eq!(mk.co_creators[1].fees_accrued, 100);
+    assert_eq!(mk.accrued_creator_fees(), 400);
+}
+
+#[test]
+fn rounding_dust_stays_with_the_creator() {
+    let mut mk = market("BTC-1");
+    mk.creator_weight = 1;
+    mk.co_creators = vec![co_creator(1), co_creator(1)];
+
+    // 100 / 3 floors to 33 per co-creator
+    mk.accrue_creator_fee(100);
+    assert_eq!(mk.co_creators[0].fees_accrued, 33);
+    assert_eq!(mk.co_creators[1].fees_accrued, 33);
+    assert_eq!(mk.creator_fees_accrued, 34);
+    assert_eq!(mk.accrued_creator_fees(), 100);
+}
+
+#[test]
+fn zero_weights_leave_everything_to_the_creator() {
+    let mut mk = market("BTC-1");
+    mk.creator_weight = 0;
+    mk.accrue_creator_fee(50);
+    assert_eq!(mk.creator_fees_accrued, 50);
+
+    // a creator with no weight of their own still keeps the dust
+    mk.co_creators = vec![co_creator(3), co_creator(3)];
+    mk.accrue_creator_fee(7);
+    assert_eq!(mk.co_creators[0].fees_accrued, 3);
+    assert_eq!(mk.co_creators[1].fees_accrued, 3);
+    assert_eq!(mk.creator_fees_accrued, 51);
+}
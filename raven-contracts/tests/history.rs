@@ -0,0 +1,46 @@
+use raven_0_1_0::*;
+
+fn history() -> MarketHistory {
+    MarketHistory {
+        market_id: "BTC-1".to_string(),
+        head: 0,
+        count: 0,
+        outcomes: [RoundOutcome::default(); HISTORY_LEN],
+    }
+}
+
+fn outcome(round_index: u32) -> RoundOutcome {
+    RoundOutcome {
+        round_index,
+        ..RoundOutcome::default()
+    }
+}
+
+#[test]
+fn history_fills_in_order() {
+    let mut history = history();
+    history.push(outcome(0));
+    history.push(outcome(1));
+    assert_eq!(history.head, 2);
+    assert_eq!(history.count, 2);
+    assert_eq!(history.outcomes[1].round_index, 1);
+}
+
+#[test]
+fn history_wraps_around_and_keeps_the_latest() {
+    let mut history = history();
+    for i in 0..HISTORY_LEN as u32 {
+        history.push(outcome(i));
+    }
+    assert_eq!(history.head, 0);
+    assert_eq!(history.count, HISTORY_LEN as u16);
+
+    history.push(outcome(HISTORY_LEN as u32));
+    history.push(outcome(HISTORY_LEN as u32 + 1));
+    assert_eq!(history.head, 2);
+    assert_eq!(history.count, HISTORY_LEN as u16);
+    // the two oldest rounds were overwritten
+    assert_eq!(history.outcomes[0].round_index, HISTORY_LEN as u32);
+    assert_eq!(history.outcomes[1].round_index, HISTORY_LEN as u32 + 1);
+    assert_eq!(history.outcomes[2].round_index, 2);
+}
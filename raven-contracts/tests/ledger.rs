@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use raven_0_1_0::*;
+
+mod common;
+use common::market;
+
+#[test]
+fn liabilities_add_up_every_obligation() {
+    let mut mk = market("BTC-1");
+    assert_eq!(mk.liabilities(), 0);
+
+    mk.open_stakes = 1_000;
+    mk.unpaid_winnings = 200;
+    mk.bond_lamports = 50;
+    mk.creator_fees_accrued = 7;
+    mk.co_creators = vec![CoCreator {
+        recipient: Pubkey::new_unique(),
+        weight: 1,
+        fees_accrued: 3,
+    }];
+    assert_eq!(mk.liabilities(), 1_260);
+}
+
+#[test]
+fn liabilities_saturate_instead_of_overflowing() {
+    let mut mk = market("BTC-1");
+    mk.open_stakes = u64::MAX;
+    mk.unpaid_winnings = 1;
+    assert_eq!(mk.liabilities(), u64::MAX);
+}
+
+#[test]
+fn unpaid_counts_only_results_still_owed() {
+    let bet = |result, refunded| Bet {
+        user: Pubkey::new_unique(),
+        amount: 10,
+        result,
+        direction: Direction::Up,
+        refunded,
+    };
+    let mut round = Round {
+        bets: vec![bet(25, false), bet(0, false), bet(15, true)],
+        ..Round::default()
+    };
+    assert_eq!(round.unpaid(), 25);
+
+    let user = round.bets[0].user;
+    assert_eq!(round.close_out(&user), (10, 25));
+    assert_eq!(round.unpaid(), 0);
+    // the losing bet still has to be recorded before the round can close
+    assert!(!round.is_closed_out());
+    let loser = round.bets[1].user;
+    assert_eq!(round.close_out(&loser), (10, 0));
+    assert!(round.is_closed_out());
+}
@@ -0,0 +1,94 @@
+use anchor_lang::{prelude::*, Discriminator};
+use raven_0_1_0::error::MarketError;
+use raven_0_1_0::legacy::*;
+use raven_0_1_0::*;
+
+const BTC_FEED: &str = "0xe62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43";
+
+fn legacy_state(feed_id: &str) -> LegacyState {
+    let admin = Pubkey::new_unique();
+    LegacyState {
+        admin_pubkey: admin,
+        escrow_pubkey: Pubkey::new_unique(),
+        escrow_bump: 254,
+        creator_fee_percent: 2,
+        allowed_pricefeeds: vec![LegacyPriceFeedConfig {
+            symbol: "BTC".to_string(),
+            pyth_feed_id: feed_id.to_string(),
+            create_market_lamports: 1_000,
+            min_betting_lamports: 10,
+            min_betting_period: 60,
+            max_betting_period: 600,
+            min_settling_period: 60,
+            max_settling_period: 600,
+        }],
+        markets: vec![LegacyMarket {
+            market_id: "btc-5m".to_string(),
+            pyth_feed_id: feed_id.to_string(),
+            creation_time: 1,
+            paused: false,
+            fee_rate: 3,
+            min_betting_price: 10,
+            betting_period: 120,
+            settling_period: 180,
+            creator_pubkey: admin,
+            round_index: 7,
+        }],
+    }
+}
+
+#[test]
+fn feed_ids_are_parsed_from_hex() {
+    let state = legacy_state(BTC_FEED).into_current().unwrap();
+    let pf = &state.allowed_pricefeeds[0];
+    let mk = &state.markets[0];
+
+    assert_eq!(pf.feed_id[..4], [0xe6, 0x2d, 0xf6, 0xc8]);
+    assert_eq!(mk.feed_id, pf.feed_id);
+    assert_eq!(pf.status, FeedStatus::Listed);
+    assert_eq!(mk.provider, OracleProvider::PythPull);
+    assert_eq!(state.creator_fee_bps, 200);
+    assert_eq!(mk.fee_rate_bps, 300);
+    assert_eq!(mk.round_index, 7);
+    assert!(mk.legacy_escrow);
+}
+
+#[test]
+fn unparseable_feed_id_fails_migration() {
+    let err = legacy_state("BTC/USD").into_current().unwrap_err();
+    assert_eq!(err, MarketError::InvalidPriceFeed.into());
+}
+
+#[test]
+fn market_ids_too_long_for_a_vault_fail_migration() {
+    let mut legacy = legacy_state(BTC_FEED);
+    legacy.markets[0].market_id = "m".repeat(MAX_MARKET_ID_LEN);
+    assert!(legacy.into_current().is_ok());
+
+    let mut legacy = legacy_state(BTC_FEED);
+    legacy.markets[0].market_id = "m".repeat(MAX_MARKET_ID_LEN + 1);
+    let err = legacy.into_current().unwrap_err();
+    assert_eq!(err, MarketError::MarketIdTooLong.into());
+}
+
+#[test]
+fn legacy_rounds_load_in_the_current_layout() {
+    let user = Pubkey::new_unique();
+    let mut data = Round::DISCRIMINATOR.to_vec();
+    // baseline fields: market_id was never written
+    String::new().serialize(&mut data).unwrap();
+    (1_000u32, 1_120u32, 6_500_000u64, 6_600_000u64, 30u64, 0u64).serialize(&mut data).unwrap();
+    vec![Bet { user, amount: 30, result: 30, direction: Direction::Up, refunded: false }]
+        .serialize(&mut data)
+        .unwrap();
+    true.serialize(&mut data).unwrap();
+    // the account was allocated for a 100 byte market_id and 200 bets
+    let legacy_space = ANCHOR_DISCRIMINATOR + 4 + 100 + 4 * 2 + 8 * 4 + 4 + 200 * 50 + 1;
+    data.resize(legacy_space, 0);
+
+    let round = Round::try_deserialize(&mut &data[..]).unwrap();
+    assert!(round.market_id.is_empty());
+    assert!(round.settled && !round.voided);
+    assert_eq!(round.unpaid(), 30);
+    assert_eq!(round.disputed_at, 0);
+}
@@ -0,0 +1,102 @@
+#![cfg(feature = "test-oracle")]
+
+use anchor_lang::prelude::*;
+use raven_0_1_0::error::MarketError;
+use raven_0_1_0::oracle::ReadPolicy;
+use raven_0_1_0::*;
+
+const FEED: [u8; 32] = [7; 32];
+
+fn mock_data(price: u64, publish_time: i64) -> Vec<u8> {
+    let mut data = Vec::new();
+    MockPrice {
+        feed_id: FEED,
+        price,
+        conf: 10,
+        publish_time,
+    }
+    .try_serialize(&mut data)
+    .unwrap();
+    data
+}
+
+const POLICY: ReadPolicy = ReadPolicy {
+    maximum_age: 60,
+    min_verification: PythVerification::Full,
+    max_conf_bps: 100,
+    published_after: None,
+    published_by: None,
+};
+
+fn clock(now: i64) -> Clock {
+    Clock {
+        unix_timestamp: now,
+        ..Clock::default()
+    }
+}
+
+#[test]
+fn every_provider_reads_the_mock_account() {
+    let key = Pubkey::new_unique();
+    let mut lamports = 0;
+    let mut data = mock_data(6_500_000, 1_000);
+    let account = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &ID, false, 0);
+
+    for provider in [OracleProvider::PythPull, OracleProvider::SwitchboardOnDemand, OracleProvider::Manual] {
+        let price = provider.read(&account, &FEED, &clock(1_030), &POLICY).unwrap();
+        assert_eq!(price.price, 6_500_000);
+        assert_eq!(price.publish_time, 1_000);
+    }
+}
+
+#[test]
+fn mock_reads_keep_the_usual_checks() {
+    let key = Pubkey::new_unique();
+    let mut lamports = 0;
+    let mut data = mock_data(6_500_000, 1_000);
+    let account = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &ID, false, 0);
+
+    let err = OracleProvider::PythPull.read(&account, &FEED, &clock(1_061), &POLICY).unwrap_err();
+    assert_eq!(err, MarketError::PriceStale.into());
+    let err = OracleProvider::PythPull.read(&account, &[8; 32], &clock(1_000), &POLICY).unwrap_err();
+    assert_eq!(err, MarketError::InvalidOracleAccount.into());
+
+    let other_owner = Pubkey::new_unique();
+    let mut lamports = 0;
+    let mut data = mock_data(6_500_000, 1_000);
+    let foreign = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &other_owner, false, 0);
+    let err = OracleProvider::PythPull.read(&foreign, &FEED, &clock(1_000), &POLICY).unwrap_err();
+    assert_eq!(err, MarketError::InvalidOracleAccount.into());
+
+    let mut lamports = 0;
+    let mut data = mock_data(0, 1_000);
+    let zero = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &ID, false, 0);
+    let err = OracleProvider::PythPull.read(&zero, &FEED, &clock(1_000), &POLICY).unwrap_err();
+    assert_eq!(err, MarketError::InvalidOraclePrice.into());
+}
+
+#[test]
+fn mock_reads_follow_the_market_policy() {
+    let key = Pubkey::new_unique();
+    let mut lamports = 0;
+    // conf of 10 on a price of 1_000 is 100 bps
+    let mut data = mock_data(1_000, 1_000);
+    let account = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &ID, false, 0);
+
+    OracleProvider::PythPull.read(&account, &FEED, &clock(1_000), &POLICY).unwrap();
+    let tight = ReadPolicy { max_conf_bps: 99, ..POLICY };
+    let err = OracleProvider::PythPull.read(&account, &FEED, &clock(1_000), &tight).unwrap_err();
+    assert_eq!(err, MarketError::ConfidenceTooHigh.into());
+
+    let closing = ReadPolicy { published_by: Some(1_000), ..POLICY };
+    OracleProvider::PythPull.read(&account, &FEED, &clock(1_010), &closing).unwrap();
+    let closing = ReadPolicy { published_by: Some(999), ..POLICY };
+    let err = OracleProvider::PythPull.read(&account, &FEED, &clock(1_010), &closing).unwrap_err();
+    assert_eq!(err, MarketError::PricePublishedLate.into());
+
+    let closing = ReadPolicy { published_after: Some(1_000), ..POLICY };
+    OracleProvider::PythPull.read(&account, &FEED, &clock(1_010), &closing).unwrap();
+    let closing = ReadPolicy { published_after: Some(1_001), ..POLICY };
+    let err = OracleProvider::PythPull.read(&account, &FEED, &clock(1_010), &closing).unwrap_err();
+    assert_eq!(err, MarketError::PricePublishedEarly.into());
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use raven_0_1_0::error::MarketError;
+use raven_0_1_0::oracle::{median, scale_price};
+use raven_0_1_0::*;
+
+#[test]
+fn prices_are_scaled_to_price_decimals() {
+    // 65_000.12345678 at pyth's usual expo
+    assert_eq!(scale_price(6_500_012_345_678, -8).unwrap(), 6_500_012_345_678);
+    // 1.5 with 5 decimals
+    assert_eq!(scale_price(150_000, -5).unwrap(), 150_000_000);
+    // extra precision is truncated
+    assert_eq!(scale_price(1_234_567_891, -10).unwrap(), 12_345_678);
+    assert_eq!(scale_price(3, 2).unwrap(), 30_000_000_000);
+}
+
+#[test]
+fn unrepresentable_prices_are_rejected() {
+    assert_eq!(scale_price(-1, -8).unwrap_err(), MarketError::InvalidOraclePrice.into());
+    assert_eq!(scale_price(u64::MAX as i128, 0).unwrap_err(), MarketError::InvalidOraclePrice.into());
+}
+
+#[test]
+fn feed_ids_are_parsed_per_provider() {
+    let hex = "0xe62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43";
+    let pyth = OracleProvider::PythPull.parse_feed_id(hex).unwrap();
+    assert_eq!(pyth[0], 0xe6);
+    assert_eq!(OracleProvider::Manual.parse_feed_id(hex).unwrap(), pyth);
+
+    let feed = Pubkey::new_unique();
+    let sb = OracleProvider::SwitchboardOnDemand.parse_feed_id(&feed.to_string()).unwrap();
+    assert_eq!(sb, feed.to_bytes());
+
+    let err = OracleProvider::SwitchboardOnDemand.parse_feed_id(hex).unwrap_err();
+    assert_eq!(err, MarketError::InvalidPriceFeed.into());
+    let err = OracleProvider::PythPull.parse_feed_id("BTC/USD").unwrap_err();
+    assert_eq!(err, MarketError::InvalidPriceFeed.into());
+}
+
+#[test]
+fn median_of_odd_and_even_source_counts() {
+    let m = median(&mut [101, 99, 100]);
+    assert_eq!(m.price, 100);
+    assert_eq!(m.spread_bps, 200);
+
+    let m = median(&mut [10_000, 10_050]);
+    assert_eq!(m.price, 10_025);
+    assert_eq!(m.spread_bps, 49);
+
+    let m = median(&mut [42]);
+    assert_eq!(m.price, 42);
+    assert_eq!(m.spread_bps, 0);
+}
+
+#[test]
+fn one_bad_source_out_of_three_moves_the_median_little() {
+    let m = median(&mut [6_500_000, 6_501_000, 1]);
+    assert_eq!(m.price, 6_500_000);
+    // still flagged, so the round is voided under any sane bound
+    assert!(m.spread_bps > 9_000);
+}
+
+#[test]
+fn weakest_verification_wins() {
+    let full = Some(PythVerification::Full);
+    let three = Some(PythVerification::Partial { num_signatures: 3 });
+    let five = Some(PythVerification::Partial { num_signatures: 5 });
+
+    assert_eq!(PythVerification::weakest(full, full), full);
+    assert_eq!(PythVerification::weakest(full, five), five);
+    assert_eq!(PythVerification::weakest(three, full), three);
+    assert_eq!(PythVerification::weakest(five, three), three);
+    // providers other than pyth carry no level
+    assert_eq!(PythVerification::weakest(None, full), full);
+    assert_eq!(PythVerification::weakest(None, None), None);
+}
+
+#[test]
+fn market_policy_stays_within_feed_limits() {
+    let limits = OraclePolicy { max_price_age: 60, max_conf_bps: 200, max_publish_delay: 30 };
+    let tighter = OraclePolicy { max_price_age: 20, max_conf_bps: 50, max_publish_delay: 30 };
+    assert!(tighter.is_valid() && tighter.within(&limits));
+    assert!(!OraclePolicy { max_price_age: 61, ..tighter }.within(&limits));
+    assert!(!OraclePolicy { max_price_age: 0, ..tighter }.is_valid());
+    assert!(!OraclePolicy { max_conf_bps: 0, ..tighter }.is_valid());
+
+    let mut loose = OraclePolicy { max_price_age: 600, max_conf_bps: 20, max_publish_delay: 300 };
+    loose.clamp_to(&limits);
+    assert_eq!(loose, OraclePolicy { max_price_age: 60, max_conf_bps: 20, max_publish_delay: 30 });
+}
+
+#[test]
+fn spot_close_window_ends_once_no_fresh_price_can_fall_inside_it() {
+    let policy = OraclePolicy { max_price_age: 60, max_conf_bps: 200, max_publish_delay: 30 };
+    // a price published at 1_030 is still fresh at 1_090
+    assert!(!policy.close_window_missed(1_000, 1_090));
+    assert!(policy.close_window_missed(1_000, 1_091));
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use raven_0_1_0::*;
+
+mod common;
+use common::price_feed;
+
+fn locked_round() -> Round {
+    Round {
+        start_time: 1_000,
+        end_time: 1_120,
+        start_price: 6_500_000,
+        total_up: 30,
+        total_down: 20,
+        bets: vec![
+            Bet { user: Pubkey::new_unique(), amount: 30, result: 0, direction: Direction::Up, refunded: false },
+            Bet { user: Pubkey::new_unique(), amount: 20, result: 0, direction: Direction::Down, refunded: false },
+        ],
+        ..Round::default()
+    }
+}
+
+#[test]
+fn rounds_stall_only_after_the_grace_period() {
+    let round = locked_round();
+    assert!(!round.is_stalled(1_180, 60));
+    assert!(round.is_stalled(1_181, 60));
+    // feeds without a grace period never auto-void
+    assert!(!round.is_stalled(u32::MAX, 0));
+    // rounds that were never locked hold no stakes
+    assert!(!Round::default().is_stalled(u32::MAX, 60));
+}
+
+#[test]
+fn voiding_returns_every_stake() {
+    let mut round = locked_round();
+    assert_eq!(round.void(), 50);
+    assert!(round.voided && round.settled);
+    assert!(round.bets.iter().all(|b| b.result == b.amount));
+    assert!(!round.is_stalled(u32::MAX, 60));
+}
+
+#[test]
+fn breaker_trips_on_large_moves() {
+    let breaker = CircuitBreaker {
+        max_move_bps: 1_000,
+        max_reference_deviation_bps: 500,
+        dispute_timeout: 3_600,
+    };
+    assert!(breaker.is_valid());
+    assert!(!breaker.trips(100_000, 110_000, None));
+    assert!(breaker.trips(100_000, 110_100, None));
+    assert!(breaker.trips(100_000, 89_900, None));
+    // within the move limit but far from the EMA
+    assert!(!breaker.trips(100_000, 105_000, Some(101_000)));
+    assert!(breaker.trips(100_000, 105_000, Some(99_000)));
+
+    assert!(!CircuitBreaker::default().trips(1, u64::MAX, Some(1)));
+    assert!(!CircuitBreaker { dispute_timeout: 0, ..breaker }.is_valid());
+}
+
+#[test]
+fn unreviewed_disputes_lapse() {
+    let mut round = locked_round();
+    round.end_price = 9_000_000;
+    round.disputed_at = 1_130;
+    assert!(!round.dispute_expired(1_190, 60));
+    assert!(round.dispute_expired(1_191, 60));
+    // a disputed round is not an outage
+    assert!(!round.is_stalled(u32::MAX, 60));
+}
+
+#[test]
+fn upheld_disputes_cannot_be_voided_as_outages() {
+    let mut round = locked_round();
+    round.end_price = 9_000_000;
+    round.disputed_at = 1_130;
+    // resolve_dispute(uphold = true)
+    round.disputed_at = 0;
+    round.voided = false;
+    assert!(!round.dispute_expired(u32::MAX, 60));
+    assert!(!round.is_stalled(u32::MAX, 60));
+}
+
+#[test]
+fn reference_checks_need_a_provider_with_an_ema() {
+    let mut pf = price_feed("BTC");
+    pf.breaker = CircuitBreaker {
+        max_move_bps: 0,
+        max_reference_deviation_bps: 500,
+        dispute_timeout: 3_600,
+    };
+    assert!(pf.breaker_valid());
+    for provider in [OracleProvider::SwitchboardOnDemand, OracleProvider::Manual] {
+        pf.provider = provider;
+        assert!(!pf.breaker_valid());
+    }
+    pf.breaker.max_reference_deviation_bps = 0;
+    pf.breaker.max_move_bps = 1_000;
+    assert!(pf.breaker_valid());
+}
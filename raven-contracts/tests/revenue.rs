@@ -0,0 +1,26 @@
+use raven_0_1_0::*;
+
+#[test]
+fn split_sends_rounding_dust_to_operations() {
+    let split = RevenueSplit {
+        operations_bps: 3_334,
+        buyback_bps: 3_333,
+        staking_bps: 3_333,
+    };
+    assert!(split.is_valid());
+    // 3_333 bps of 100 is 33.33, floored to 33 for both vaults
+    assert_eq!(split.split(100), (34, 33, 33));
+    assert_eq!(split.split(1), (1, 0, 0));
+    assert_eq!(split.split(0), (0, 0, 0));
+
+    let (operations, buyback, staking) = split.split(u64::MAX);
+    assert_eq!(operations as u128 + buyback as u128 + staking as u128, u64::MAX as u128);
+}
+
+#[test]
+fn split_must_cover_the_whole_fee() {
+    assert!(RevenueSplit::default().is_valid());
+    assert_eq!(RevenueSplit::default().split(1_000), (1_000, 0, 0));
+    assert!(!RevenueSplit { operations_bps: 5_000, buyback_bps: 4_000, staking_bps: 0 }.is_valid());
+    assert!(!RevenueSplit { operations_bps: 5_000, buyback_bps: 5_000, staking_bps: 1 }.is_valid());
+}
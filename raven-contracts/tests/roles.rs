@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use raven_0_1_0::error::MarketError;
+use raven_0_1_0::*;
+
+mod common;
+use common::state;
+
+fn roles() -> Roles {
+    Roles { grants: vec![] }
+}
+
+#[test]
+fn admin_holds_every_role() {
+    let admin = Pubkey::new_unique();
+    let state = state(admin);
+
+    state.require_role(None, &admin, Role::Keeper).unwrap();
+    state.require_role(Some(&roles()), &admin, Role::Treasurer).unwrap();
+}
+
+#[test]
+fn grant_is_scoped_to_its_role() {
+    let state = state(Pubkey::new_unique());
+    let keeper = Pubkey::new_unique();
+    let mut roles = roles();
+    roles.grant(keeper, Role::Keeper).unwrap();
+
+    state.require_role(Some(&roles), &keeper, Role::Keeper).unwrap();
+    let err = state.require_role(Some(&roles), &keeper, Role::FeedManager).unwrap_err();
+    assert_eq!(err, MarketError::MissingRole.into());
+    let err = state.require_role(None, &keeper, Role::Keeper).unwrap_err();
+    assert_eq!(err, MarketError::MissingRole.into());
+}
+
+#[test]
+fn roles_accumulate_on_one_grant() {
+    let key = Pubkey::new_unique();
+    let mut roles = roles();
+    roles.grant(key, Role::Pauser).unwrap();
+    roles.grant(key, Role::MarketOperator).unwrap();
+    roles.grant(key, Role::Pauser).unwrap();
+
+    assert_eq!(roles.grants.len(), 1);
+    assert!(roles.has(&key, Role::Pauser));
+    assert!(roles.has(&key, Role::MarketOperator));
+}
+
+#[test]
+fn revoking_last_role_drops_the_grant() {
+    let key = Pubkey::new_unique();
+    let mut roles = roles();
+    roles.grant(key, Role::Pauser).unwrap();
+    roles.grant(key, Role::Keeper).unwrap();
+
+    roles.revoke(&key, Role::Pauser).unwrap();
+    assert!(!roles.has(&key, Role::Pauser));
+    assert!(roles.has(&key, Role::Keeper));
+
+    roles.revoke(&key, Role::Keeper).unwrap();
+    assert!(roles.grants.is_empty());
+    assert_eq!(roles.revoke(&key, Role::Keeper).unwrap_err(), MarketError::MissingRole.into());
+}
+
+#[test]
+fn grants_are_capped() {
+    let mut roles = roles();
+    for _ in 0..MAX_ROLE_GRANTS {
+        roles.grant(Pubkey::new_unique(), Role::Keeper).unwrap();
+    }
+    let err = roles.grant(Pubkey::new_unique(), Role::Keeper).unwrap_err();
+    assert_eq!(err, MarketError::InvalidArgument.into());
+}
@@ -0,0 +1,43 @@
+use raven_0_1_0::error::MarketError;
+use raven_0_1_0::*;
+
+mod common;
+use common::{market, price_feed};
+
+#[test]
+fn twap_averages_recorded_samples() {
+    let mut round = Round::default();
+    assert_eq!(round.twap(), None);
+
+    round.record_sample(100, 10);
+    round.record_sample(110, 20);
+    round.record_sample(121, 30);
+    assert_eq!(round.twap_samples, 3);
+    assert_eq!(round.last_sample_time, 30);
+    assert_eq!(round.twap(), Some(110));
+}
+
+#[test]
+fn twap_schedule_must_fit_the_settling_window() {
+    assert!(SettlementMode::Spot.is_valid(0));
+    assert!(SettlementMode::Twap { sample_interval: 10, min_samples: 7 }.is_valid(60));
+    assert!(!SettlementMode::Twap { sample_interval: 10, min_samples: 8 }.is_valid(60));
+    assert!(!SettlementMode::Twap { sample_interval: 0, min_samples: 3 }.is_valid(60));
+    assert!(!SettlementMode::Twap { sample_interval: 10, min_samples: 0 }.is_valid(60));
+}
+
+#[test]
+fn feed_updates_cannot_break_a_twap_schedule() {
+    let mut mk = market("BTC-1");
+    mk.settling_period = 120;
+    mk.settlement = SettlementMode::Twap { sample_interval: 10, min_samples: 10 };
+
+    let mut pf = price_feed("BTC");
+    mk.apply_feed_limits(&pf).unwrap();
+    assert_eq!(mk.settling_period, 120);
+
+    // 60 seconds fit only 7 samples 10 seconds apart
+    pf.max_settling_period = 60;
+    let err = mk.apply_feed_limits(&pf).unwrap_err();
+    assert_eq!(err, MarketError::InvalidArgument.into());
+}
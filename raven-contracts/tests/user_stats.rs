@@ -0,0 +1,45 @@
+use raven_0_1_0::*;
+
+#[test]
+fn wins_and_losses_are_counted_with_pnl() {
+    let mut stats = UserStats::default();
+    stats.record_round(100, 180);
+    assert_eq!((stats.wins, stats.losses), (1, 0));
+    assert_eq!(stats.total_won, 180);
+    assert_eq!(stats.net_pnl, 80);
+
+    stats.record_round(50, 0);
+    assert_eq!((stats.wins, stats.losses), (1, 1));
+    assert_eq!(stats.total_won, 180);
+    assert_eq!(stats.net_pnl, 30);
+}
+
+#[test]
+fn returning_the_exact_stake_is_neither() {
+    let mut stats = UserStats::default();
+    stats.record_round(100, 150);
+    // a voided round returns exactly what was staked
+    stats.record_round(70, 70);
+    assert_eq!((stats.wins, stats.losses), (1, 0));
+    assert_eq!(stats.current_streak, 1);
+    assert_eq!(stats.total_won, 220);
+    assert_eq!(stats.net_pnl, 50);
+}
+
+#[test]
+fn streaks_restart_when_the_outcome_flips() {
+    let mut stats = UserStats::default();
+    stats.record_round(10, 20);
+    stats.record_round(10, 20);
+    assert_eq!(stats.current_streak, 2);
+
+    stats.record_round(10, 0);
+    assert_eq!(stats.current_streak, -1);
+    stats.record_round(10, 5);
+    assert_eq!(stats.current_streak, -2);
+
+    stats.record_round(10, 11);
+    assert_eq!(stats.current_streak, 1);
+    assert_eq!((stats.wins, stats.losses), (3, 2));
+    assert_eq!(stats.net_pnl, 6);
+}